@@ -0,0 +1,34 @@
+/* Generated from src/ffi.rs, do not edit. Regenerate with `AOC_UPDATE_HEADER=1 cargo test ffi` */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/* Success, the answer is in out_buf */
+#define AOC_OK 0
+/* input or out_buf is NULL */
+#define AOC_ERR_NULL_POINTER 1
+/* No solutions for this year */
+#define AOC_ERR_UNKNOWN_YEAR 2
+/* No solution for this day and part */
+#define AOC_ERR_UNKNOWN_SOLUTION 3
+/* The input is not valid UTF-8 */
+#define AOC_ERR_INVALID_UTF8 4
+/* out_buf can't hold the answer and its terminating NUL */
+#define AOC_ERR_BUFFER_TOO_SMALL 5
+/* The solution panicked, most likely on malformed input */
+#define AOC_ERR_PANIC 6
+
+/*
+ * Solves a puzzle, writing the answer as NUL-terminated string into out_buf.
+ *
+ * The length of the answer (without NUL) is written to answer_len when it isn't NULL,
+ * also when out_buf is too small so that the caller can retry with a larger buffer.
+ * Returns AOC_OK or one of the AOC_ERR_* codes.
+ */
+int32_t aoc_solve(uint32_t year, uint32_t day, uint32_t part,
+                  const uint8_t *input, size_t input_len,
+                  char *out_buf, size_t out_len, size_t *answer_len);
+
+#endif /* AOC_H */
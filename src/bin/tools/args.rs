@@ -0,0 +1,54 @@
+/* Minimal option parsing, options are given as `--name value` or as `--flag` */
+
+pub fn flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|arg| arg == name)
+}
+
+pub fn value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+pub fn number(args: &[String], name: &str) -> Result<u32, String> {
+    let raw = value(args, name).ok_or(format!("Missing {} <number>", name))?;
+    raw.parse::<u32>()
+        .map_err(|_| format!("Invalid number for {}: {}", name, raw))
+}
+
+pub fn day(args: &[String]) -> Result<u32, String> {
+    number(args, "--day")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn to_args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_value() {
+        let args = to_args("--day 5 --input foo.txt");
+        assert_eq!(value(&args, "--input"), Some("foo.txt"));
+        assert_eq!(value(&args, "--part"), None);
+        assert_eq!(value(&to_args("--input"), "--input"), None);
+    }
+
+    #[test]
+    fn test_flag() {
+        let args = to_args("--day 5 --stream");
+        assert!(flag(&args, "--stream"));
+        assert!(!flag(&args, "--input"));
+    }
+
+    #[test]
+    fn test_day() {
+        assert_eq!(day(&to_args("--day 12")), Ok(12));
+        assert!(day(&to_args("--day twelve")).is_err());
+        assert!(day(&to_args("")).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use crate::args;
+use crate::json;
+use crate::results::{self, Record};
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/* Compares the answers and timings of two `results` runs: saved result files, other git revisions
+ * (built in a temporary worktree) or the current build */
+
+const DIFF_DIR: &str = "target/diff";
+
+#[derive(Debug, PartialEq)]
+struct Summary {
+    day: u32,
+    part: u32,
+    inputs: usize,
+    mismatches: usize,
+    old_ms: f64,
+    new_ms: f64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Comparison {
+    summaries: Vec<Summary>,
+    // (day, part, input, old answer, new answer)
+    mismatches: Vec<(u32, u32, String, String, String)>,
+    // Results of one side without a counterpart, i.e. when the inputs changed in between
+    only_old: Vec<(u32, u32, String)>,
+    only_new: Vec<(u32, u32, String)>,
+}
+
+fn compare(old: &[Record], new: &[Record]) -> Comparison {
+    let find = |records: &[Record], record: &Record| {
+        records
+            .iter()
+            .position(|r| (r.day, r.part, &r.input) == (record.day, record.part, &record.input))
+    };
+    let mut comparison = Comparison {
+        summaries: Vec::new(),
+        mismatches: Vec::new(),
+        only_old: Vec::new(),
+        only_new: Vec::new(),
+    };
+    for old_record in old {
+        let new_record = match find(new, old_record) {
+            Some(index) => &new[index],
+            None => {
+                comparison.only_old.push((
+                    old_record.day,
+                    old_record.part,
+                    old_record.input.clone(),
+                ));
+                continue;
+            }
+        };
+        let position = comparison
+            .summaries
+            .iter()
+            .position(|s| (s.day, s.part) == (old_record.day, old_record.part));
+        let summary = match position {
+            Some(index) => &mut comparison.summaries[index],
+            None => {
+                comparison.summaries.push(Summary {
+                    day: old_record.day,
+                    part: old_record.part,
+                    inputs: 0,
+                    mismatches: 0,
+                    old_ms: 0.0,
+                    new_ms: 0.0,
+                });
+                comparison.summaries.last_mut().unwrap()
+            }
+        };
+        summary.inputs += 1;
+        summary.old_ms += old_record.time_ms;
+        summary.new_ms += new_record.time_ms;
+        if old_record.answer != new_record.answer {
+            summary.mismatches += 1;
+            comparison.mismatches.push((
+                old_record.day,
+                old_record.part,
+                old_record.input.clone(),
+                old_record.answer.clone(),
+                new_record.answer.clone(),
+            ));
+        }
+    }
+    for new_record in new {
+        if find(old, new_record).is_none() {
+            comparison
+                .only_new
+                .push((new_record.day, new_record.part, new_record.input.clone()));
+        }
+    }
+    comparison
+        .summaries
+        .sort_by_key(|summary| (summary.day, summary.part));
+    comparison
+}
+
+fn change(old_ms: f64, new_ms: f64) -> String {
+    if old_ms == 0.0 {
+        return "-".to_string();
+    }
+    format!("{:+.1}%", (new_ms - old_ms) / old_ms * 100.0)
+}
+
+/* Multi-line answers (day 10) on one line */
+fn show(answer: &str) -> String {
+    match answer.contains('\n') {
+        true => json::string(answer),
+        false => answer.to_string(),
+    }
+}
+
+fn report(comparison: &Comparison) -> String {
+    let mut report = format!(
+        "{:>3}  {:>4}  {:>6}  {:>10}  {:>10}  {:>10}  {:>7}\n",
+        "Day", "Part", "Inputs", "Mismatches", "Old ms", "New ms", "Change"
+    );
+    for summary in &comparison.summaries {
+        report += &format!(
+            "{:>3}  {:>4}  {:>6}  {:>10}  {:>10.3}  {:>10.3}  {:>7}\n",
+            summary.day,
+            summary.part,
+            summary.inputs,
+            summary.mismatches,
+            summary.old_ms,
+            summary.new_ms,
+            change(summary.old_ms, summary.new_ms)
+        );
+    }
+    for (day, part, input, old, new) in &comparison.mismatches {
+        report += &format!(
+            "Day {} - Part {}: MISMATCH on {}, {} became {}\n",
+            day,
+            part,
+            input,
+            show(old),
+            show(new)
+        );
+    }
+    for (side, missing) in [("old", &comparison.only_old), ("new", &comparison.only_new)] {
+        for (day, part, input) in missing {
+            report += &format!(
+                "Day {} - Part {}: {} only in the {} results\n",
+                day, part, input, side
+            );
+        }
+    }
+    report
+}
+
+fn git(args: &[&str]) -> Result<(), String> {
+    let status = Command::new("git")
+        .args(args)
+        .status()
+        .map_err(|e| format!("Could not run git: {}", e))?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(format!("git {} failed", args.join(" "))),
+    }
+}
+
+/* Options of `results` given to `diff`, for running other revisions the same way */
+fn results_args(args: &[String]) -> Vec<String> {
+    let mut passed = Vec::new();
+    for name in ["--day", "--generated", "--seed"] {
+        if let Some(value) = args::value(args, name) {
+            passed.push(name.to_string());
+            passed.push(value.to_string());
+        }
+    }
+    passed
+}
+
+/* Runs `results` as built from another revision, on the inputs of the current checkout. That
+ * revision needs to have the `results` command */
+fn results_at(rev: &str, args: &[String]) -> Result<Vec<Record>, String> {
+    let name: String = rev
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let worktree = Path::new(DIFF_DIR).join(&name);
+    let output = env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(DIFF_DIR)
+        .join(format!("{}.json", name));
+    if worktree.exists() {
+        git(&["worktree", "remove", "--force", &worktree.to_string_lossy()])?;
+    }
+    git(&[
+        "worktree",
+        "add",
+        "--detach",
+        &worktree.to_string_lossy(),
+        rev,
+    ])?;
+
+    eprintln!("Running the results of {}", rev);
+    let mut cargo = Command::new("cargo");
+    cargo
+        .args(["run", "--quiet", "--bin", "tools"])
+        .arg("--manifest-path")
+        .arg(worktree.join("Cargo.toml"))
+        // Shared between revisions, so that building another one is incremental
+        .env("CARGO_TARGET_DIR", Path::new(DIFF_DIR).join("target"));
+    // Timings are only comparable between builds with the same profile
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let status = cargo
+        .args(["--", "results", "--output"])
+        .arg(&output)
+        .args(results_args(args))
+        .status();
+    git(&["worktree", "remove", "--force", &worktree.to_string_lossy()])?;
+    match status {
+        Ok(status) if status.success() => results::read(&output),
+        Ok(_) => Err(format!("Running the results of {} failed", rev)),
+        Err(e) => Err(format!("Could not run cargo: {}", e)),
+    }
+}
+
+fn load(args: &[String], file: &str, rev: &str) -> Result<Option<Vec<Record>>, String> {
+    match (args::value(args, file), args::value(args, rev)) {
+        (Some(_), Some(_)) => Err(format!("Give either {} or {}", file, rev)),
+        (Some(path), None) => results::read(&PathBuf::from(path)).map(Some),
+        (None, Some(rev)) => results_at(rev, args).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+pub fn diff(args: &[String]) -> Result<(), String> {
+    let old = load(args, "--old", "--old-rev")?
+        .ok_or("Missing --old FILE or --old-rev REV".to_string())?;
+    let new = match load(args, "--new", "--new-rev")? {
+        Some(new) => new,
+        None => {
+            let (day, generated, seed) = results::options(args)?;
+            results::collect_quietly(day, generated, seed)
+        }
+    };
+
+    let comparison = compare(&old, &new);
+    print!("{}", report(&comparison));
+    if !comparison.mismatches.is_empty() {
+        return Err(format!("{} answer(s) differ", comparison.mismatches.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::fs;
+
+    fn record(day: u32, part: u32, input: &str, answer: &str, time_ms: f64) -> Record {
+        Record {
+            day,
+            part,
+            input: input.to_string(),
+            answer: answer.to_string(),
+            time_ms,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let old = vec![
+            record(6, 1, "real", "1100", 1.0),
+            record(6, 1, "generated #0", "7", 2.0),
+            record(1, 1, "real", "24000", 0.5),
+            record(6, 2, "generated #1", "19", 1.0),
+        ];
+        let new = vec![
+            record(1, 1, "real", "24000", 0.25),
+            record(6, 1, "real", "1100", 0.5),
+            record(6, 1, "generated #0", "8", 0.5),
+            record(6, 2, "generated #2", "3", 1.0),
+        ];
+        let comparison = compare(&old, &new);
+        assert_eq!(
+            comparison.summaries,
+            vec![
+                Summary {
+                    day: 1,
+                    part: 1,
+                    inputs: 1,
+                    mismatches: 0,
+                    old_ms: 0.5,
+                    new_ms: 0.25
+                },
+                Summary {
+                    day: 6,
+                    part: 1,
+                    inputs: 2,
+                    mismatches: 1,
+                    old_ms: 3.0,
+                    new_ms: 1.0
+                }
+            ]
+        );
+        assert_eq!(
+            report(&comparison),
+            "Day  Part  Inputs  Mismatches      Old ms      New ms   Change
+  1     1       1           0       0.500       0.250   -50.0%
+  6     1       2           1       3.000       1.000   -66.7%
+Day 6 - Part 1: MISMATCH on generated #0, 7 became 8
+Day 6 - Part 2: generated #1 only in the old results
+Day 6 - Part 2: generated #2 only in the new results
+"
+        );
+    }
+
+    #[test]
+    fn test_show() {
+        assert_eq!(show("24000"), "24000");
+        assert_eq!(show("#.\n.#"), "\"#.\\n.#\"");
+    }
+
+    #[test]
+    fn test_results_args() {
+        let args: Vec<String> = "--old-rev HEAD --seed 3 --day 6"
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        assert_eq!(results_args(&args), vec!["--day", "6", "--seed", "3"]);
+    }
+
+    #[test]
+    fn test_diff_files() {
+        let dir = env::temp_dir().join(format!("aoc-diff-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let old = dir.join("old.json");
+        let new = dir.join("new.json");
+        fs::write(&old, results::to_json(&[record(1, 1, "real", "1", 1.0)])).unwrap();
+        fs::write(&new, results::to_json(&[record(1, 1, "real", "1", 2.0)])).unwrap();
+        let args = |old: &Path, new: &Path| {
+            vec![
+                "--old".to_string(),
+                old.to_string_lossy().to_string(),
+                "--new".to_string(),
+                new.to_string_lossy().to_string(),
+            ]
+        };
+        assert_eq!(diff(&args(&old, &new)), Ok(()));
+        fs::write(&new, results::to_json(&[record(1, 1, "real", "2", 2.0)])).unwrap();
+        assert_eq!(
+            diff(&args(&old, &new)),
+            Err("1 answer(s) differ".to_string())
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{args, input};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/* Extracts sample inputs and their expected answers from a saved puzzle page (i.e. `day5.html`) */
+
+fn sample_path(day: u32, part: Option<u32>) -> PathBuf {
+    match part {
+        None => Path::new(input::INPUT_DIR).join(format!("day{}.sample.txt", day)),
+        Some(part) => {
+            Path::new(input::INPUT_DIR).join(format!("day{}.sample.part{}.txt", day, part))
+        }
+    }
+}
+
+fn expected_path(day: u32) -> PathBuf {
+    Path::new(input::INPUT_DIR).join(format!("day{}.sample.expected.txt", day))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+}
+
+/* Contents of all `<open>...</close>` elements, in order */
+fn elements<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        let after_open = &rest[start + open.len()..];
+        // Skip the attributes of the opening tag, if any
+        let content_start = match after_open.find('>') {
+            Some(position) => position + 1,
+            None => break,
+        };
+        let content = &after_open[content_start..];
+        match content.find(close) {
+            Some(end) => {
+                found.push(&content[..end]);
+                rest = &content[end + close.len()..];
+            }
+            None => break,
+        }
+    }
+    found
+}
+
+/* The description of each part that is in the page (part 2 only shows up after solving part 1) */
+fn articles(html: &str) -> Vec<&str> {
+    elements(html, "<article", "</article>")
+}
+
+fn code_blocks(article: &str) -> Vec<String> {
+    elements(article, "<pre><code", "</code></pre>")
+        .iter()
+        .map(|block| strip_tags(block))
+        .collect()
+}
+
+fn emphasized_codes(article: &str) -> Vec<String> {
+    elements(article, "<code><em", "</em></code>")
+        .iter()
+        .map(|code| strip_tags(code))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+struct Extracted {
+    sample: String,
+    part2_sample: Option<String>,
+    expected: Vec<(u32, String)>,
+}
+
+fn extract_page(html: &str, part2_block: Option<usize>) -> Result<Extracted, String> {
+    let articles = articles(html);
+    let part1 = articles.first().ok_or("No puzzle description in page")?;
+    let sample = code_blocks(part1)
+        .into_iter()
+        .next()
+        .ok_or("No <pre><code> sample in part 1")?;
+
+    // Part 2 usually reuses the sample; its code blocks tend to show intermediate states instead
+    let part2_sample = match (part2_block, articles.get(1)) {
+        (Some(block), Some(part2)) => Some(
+            block
+                .checked_sub(1)
+                .and_then(|index| code_blocks(part2).into_iter().nth(index))
+                .ok_or(format!("No <pre><code> block #{} in part 2", block))?,
+        ),
+        (Some(_), None) => return Err("Page has no part 2 (yet)".to_string()),
+        (None, _) => None,
+    };
+
+    // The answer for the sample is the last emphasized code of a part
+    let expected = articles
+        .iter()
+        .enumerate()
+        .filter_map(|(i, article)| {
+            emphasized_codes(article)
+                .pop()
+                .map(|answer| (i as u32 + 1, answer))
+        })
+        .collect();
+
+    Ok(Extracted {
+        sample,
+        part2_sample,
+        expected,
+    })
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+pub fn extract(args: &[String]) -> Result<(), String> {
+    let day = args::day(args)?;
+    let page = match args::value(args, "--page") {
+        Some(page) => PathBuf::from(page),
+        None => Path::new(input::INPUT_DIR).join(format!("day{}.html", day)),
+    };
+    let part2_block = match args::value(args, "--part2-block") {
+        Some(_) => Some(args::number(args, "--part2-block")? as usize),
+        None => None,
+    };
+
+    let extracted = extract_page(&input::read_file(&page)?, part2_block)?;
+    write(&sample_path(day, None), &extracted.sample)?;
+    if let Some(sample) = extracted.part2_sample {
+        write(&sample_path(day, Some(2)), &sample)?;
+    }
+    let expected: String = extracted
+        .expected
+        .iter()
+        .map(|(part, answer)| format!("part{}: {}\n", part, answer))
+        .collect();
+    write(&expected_path(day), &expected)?;
+    print!("{}", expected);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example, suppose the Elves finish writing their items' <code>Calories</code>:</p>
+<pre><code>1000
+2000
+
+4000
+</code></pre>
+<p>In the example above, this is <em>4000</em> Calories.</p>
+<p>Find the Elf carrying the most Calories: <code><em>4000</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>24000</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Intermediate state, with <em>highlighting</em> &amp; entities:</p>
+<pre><code>&lt;<em>1000</em>&gt;
+</code></pre>
+<p>The total is <code><em>1000</em></code>, of the top <code><em>7000</em></code>.</p>
+</article>
+</main>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_extract_page() {
+        let extracted = extract_page(PAGE, None).unwrap();
+        assert_eq!(extracted.sample, "1000\n2000\n\n4000\n");
+        assert_eq!(extracted.part2_sample, None);
+        assert_eq!(
+            extracted.expected,
+            vec![(1, "4000".to_string()), (2, "7000".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_extract_part2_block() {
+        let extracted = extract_page(PAGE, Some(1)).unwrap();
+        assert_eq!(extracted.part2_sample, Some("<1000>\n".to_string()));
+        assert!(extract_page(PAGE, Some(2)).is_err());
+        assert!(extract_page(PAGE, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_extract_part1_only() {
+        let part1_only = &PAGE[..PAGE.find("<article class=\"day-desc\"><h2 id=").unwrap()];
+        let extracted = extract_page(part1_only, None).unwrap();
+        assert_eq!(extracted.expected, vec![(1, "4000".to_string())]);
+        assert!(extract_page("<html></html>", None).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INPUT_DIR: &str = "input/2022";
+
+pub fn path(day: u32) -> PathBuf {
+    Path::new(INPUT_DIR).join(format!("day{}.txt", day))
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String, String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+pub fn read(day: u32) -> Result<String, String> {
+    read_file(path(day))
+}
+
+/* Matches the input of a day and its variants (i.e. `day5.txt`, `day5.sample.txt`) but not `day15.txt` */
+pub fn is_input_for(filename: &str, day: u32) -> bool {
+    let prefix = format!("day{}", day);
+    match filename.strip_prefix(&prefix) {
+        Some(rest) => rest.ends_with(".txt") && rest.starts_with('.'),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_is_input_for() {
+        assert!(is_input_for("day5.txt", 5));
+        assert!(is_input_for("day5.sample.txt", 5));
+        assert!(!is_input_for("day15.txt", 5));
+        assert!(!is_input_for("day5.rs", 5));
+        assert!(!is_input_for("day1.txt", 5));
+    }
+}
@@ -0,0 +1,188 @@
+/* Just enough JSON for the tools: escaping strings for the server and the result files, and reading
+ * back result files, which are arrays of flat objects holding strings and numbers */
+
+pub fn string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Number(f64),
+}
+
+pub type Object = Vec<(String, Value)>;
+
+pub fn get<'a>(object: &'a Object, key: &str) -> Option<&'a Value> {
+    object
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value)
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("Expected '{}', found '{}'", expected, c)),
+            None => Err(format!("Expected '{}', found the end", expected)),
+        }
+    }
+
+    /* Consumes `c` if it's next */
+    fn next_is(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&c).is_some()
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.chars.next()).collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .map_err(|_| format!("Invalid escape \\u{}", hex))?;
+                        value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                Some(c) => value.push(c),
+                None => break,
+            }
+        }
+        Err("Unterminated string".to_string())
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let mut raw = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c))
+        {
+            raw.push(c);
+        }
+        raw.parse::<f64>()
+            .map_err(|_| format!("Invalid number '{}'", raw))
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.chars.peek() {
+            Some('"') => Ok(Value::String(self.string()?)),
+            _ => Ok(Value::Number(self.number()?)),
+        }
+    }
+
+    fn object(&mut self) -> Result<Object, String> {
+        self.expect('{')?;
+        let mut object = Object::new();
+        if self.next_is('}') {
+            return Ok(object);
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            object.push((key, self.value()?));
+            if self.next_is('}') {
+                return Ok(object);
+            }
+            self.expect(',')?;
+        }
+    }
+}
+
+pub fn parse_objects(text: &str) -> Result<Vec<Object>, String> {
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+    };
+    parser.expect('[')?;
+    let mut objects = Vec::new();
+    if !parser.next_is(']') {
+        loop {
+            objects.push(parser.object()?);
+            if parser.next_is(']') {
+                break;
+            }
+            parser.expect(',')?;
+        }
+    }
+    parser.skip_whitespace();
+    match parser.chars.next() {
+        Some(c) => Err(format!("Unexpected '{}' after the array", c)),
+        None => Ok(objects),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("#.\n.#"), "\"#.\\n.#\"");
+        assert_eq!(string("say \"hi\""), "\"say \\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn test_parse_objects() {
+        let text = format!(
+            "[\n{{\"day\":1,\"answer\":{}}},\n{{ \"time_ms\" : -1.5e1 }}, {{}}\n]\n",
+            string("#.\n\"\u{1}")
+        );
+        assert_eq!(
+            parse_objects(&text),
+            Ok(vec![
+                vec![
+                    ("day".to_string(), Value::Number(1.0)),
+                    (
+                        "answer".to_string(),
+                        Value::String("#.\n\"\u{1}".to_string())
+                    )
+                ],
+                vec![("time_ms".to_string(), Value::Number(-15.0))],
+                vec![],
+            ])
+        );
+        assert_eq!(parse_objects(" [ ] "), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_objects("").is_err());
+        assert!(parse_objects("[{\"day\":}]").is_err());
+        assert!(parse_objects("[{\"day\":1]").is_err());
+        assert!(parse_objects("[{\"answer\":\"1}]").is_err());
+        assert!(parse_objects("[] []").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::args;
+use advent_of_code_2022::metadata::{self, Metadata, Tag};
+use advent_of_code_2022::solutions;
+
+fn tag_names(tags: &[Tag]) -> String {
+    tags.iter()
+        .map(|tag| tag.name())
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+fn table(days: &[&Metadata]) -> String {
+    let title_width = days.iter().map(|m| m.title.len()).max().unwrap_or(0);
+    let tags_width = days
+        .iter()
+        .map(|m| tag_names(m.tags).len())
+        .max()
+        .unwrap_or(0);
+    let mut table = String::new();
+    for metadata in days {
+        // Named variants show up next to the day, i.e. day 6 has a Bitmask one
+        let mut variants = solutions::SOLUTIONS
+            .iter()
+            .filter(|s| s.day == metadata.day)
+            .filter_map(|s| s.name)
+            .collect::<Vec<&str>>();
+        variants.dedup();
+        let mut complexity = metadata.complexity.to_string();
+        if !variants.is_empty() {
+            complexity += &format!(" (variants: {})", variants.join(", "));
+        }
+        table += &format!(
+            "Day {:>2}  {:title_width$}  {:tags_width$}  {}\n",
+            metadata.day,
+            metadata.title,
+            tag_names(metadata.tags),
+            complexity,
+        );
+    }
+    table
+}
+
+pub fn list(args: &[String]) -> Result<(), String> {
+    let days: Vec<&Metadata> = match args::value(args, "--tag") {
+        Some(name) => {
+            let tag = Tag::parse(name).ok_or(format!(
+                "Unknown tag {}, expected one of: {}",
+                name,
+                tag_names(&Tag::ALL)
+            ))?;
+            metadata::with_tag(tag).collect()
+        }
+        None => metadata::METADATA.iter().collect(),
+    };
+    print!("{}", table(&days));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let days = [
+            metadata::for_day(4).unwrap(),
+            metadata::for_day(15).unwrap(),
+        ];
+        let table = table(&days);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Day  4  Camp Cleanup           parsing, intervals  O(n)"));
+        assert!(lines[1].starts_with("Day 15  Beacon Exclusion Zone  intervals           O("));
+    }
+
+    #[test]
+    fn test_unknown_tag() {
+        let args = vec!["--tag".to_string(), "graphs".to_string()];
+        assert!(list(&args).unwrap_err().starts_with("Unknown tag graphs"));
+    }
+}
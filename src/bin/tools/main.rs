@@ -0,0 +1,121 @@
+mod args;
+mod diff;
+mod extract;
+mod input;
+mod json;
+mod list;
+mod results;
+mod server;
+mod submit;
+mod supervise;
+mod verify;
+mod watch;
+
+use advent_of_code_2022::overflow;
+use advent_of_code_2022::solutions::{self, STREAMING_SOLUTIONS};
+use advent_of_code_2022::utils;
+use std::env;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: cargo run --bin tools -- <command> [options]
+
+Commands:
+  run --day N [--input FILE] [--stream] [--progress] [--timeout SECS]
+                               Solve both parts of a day, with --stream without reading the whole input in memory.
+                               The slow solvers show a progress bar with --progress, and stop after --timeout
+                               with what they found so far
+  list [--tag TAG]             List the days with their titles, tags and expected complexity, optionally only
+                               those with a tag (grid, simulation, parsing, pathfinding, intervals, ...)
+  watch --day N                Re-run a day and its sample tests whenever its input or source changes
+  verify [--day N] [--generated COUNT] [--seed SEED]
+                               Check that all implementations of a part agree on real and generated inputs
+  results [--day N] [--generated COUNT] [--seed SEED] [--output FILE]
+                               Write the answers and timings of all solutions on the real, other saved and
+                               generated inputs as JSON (default 10 generated inputs per day with a generator)
+  diff (--old FILE | --old-rev REV) [--new FILE | --new-rev REV] [--day N] [--generated COUNT] [--seed SEED]
+                               Compare the answers and timings of two result sets per day and part, from files or
+                               from the results of git revisions. Compares with the current build without --new
+  serve [--port PORT]          Serve the solutions over HTTP on 127.0.0.1 (default port 8022)
+  submit --day N --part P [--answer ANSWER]
+                               Submit an answer to adventofcode.com, computed unless given
+  extract --day N [--page FILE] [--part2-block K]
+                               Write the sample and its answers from a saved puzzle page (default input/2022/dayN.html)
+
+Options:
+  --strict-overflow            Fail when numbers don't fit the types the puzzles imply, instead of widening them";
+
+fn run_streaming(day: u32, path: &Path) -> Result<(), String> {
+    let mut found = false;
+    for solution in STREAMING_SOLUTIONS.iter().filter(|s| s.day == day) {
+        found = true;
+        let mut reader =
+            utils::open(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        println!(
+            "Day {} - Part {}: {}",
+            day,
+            solution.part,
+            (solution.solve)(&mut reader)
+        );
+    }
+    if !found {
+        return Err(format!("No streaming solutions for day {}", day));
+    }
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let day = args::day(args)?;
+    if args::flag(args, "--stream") {
+        return match args::value(args, "--input") {
+            Some(path) => run_streaming(day, Path::new(path)),
+            None => run_streaming(day, &input::path(day)),
+        };
+    }
+    let content = match args::value(args, "--input") {
+        Some(path) => input::read_file(path)?,
+        None => input::read(day)?,
+    };
+
+    let show_progress = args::flag(args, "--progress");
+    let timeout = match args::value(args, "--timeout") {
+        Some(_) => Some(Duration::from_secs(args::number(args, "--timeout")? as u64)),
+        None => None,
+    };
+
+    let mut found = false;
+    for solution in solutions::for_day(day) {
+        found = true;
+        let answer = supervise::solve(solution, &content, show_progress, timeout)?;
+        println!("Day {} - Part {}: {}", day, solution.part, answer);
+    }
+    if !found {
+        return Err(format!("No solutions for day {}", day));
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    overflow::set_strict(args::flag(&args, "--strict-overflow"));
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("list") => list::list(&args[1..]),
+        Some("watch") => watch::watch(&args[1..]),
+        Some("verify") => verify::verify(&args[1..]),
+        Some("results") => results::results(&args[1..]),
+        Some("diff") => diff::diff(&args[1..]),
+        Some("serve") => server::serve(&args[1..]),
+        Some("submit") => submit::submit(&args[1..]),
+        Some("extract") => extract::extract(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
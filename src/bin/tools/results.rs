@@ -0,0 +1,248 @@
+use crate::json::{self, Value};
+use crate::{args, input};
+
+use advent_of_code_2022::generators::{self, Rng};
+use advent_of_code_2022::solutions::SOLUTIONS;
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::time::Instant;
+
+const DEFAULT_GENERATED: u32 = 10;
+const DEFAULT_SEED: u32 = 2022;
+const PANICKED: &str = "<panicked>";
+
+/* The answers of the default solutions with their timings on all inputs at hand, saved by `results`
+ * so that `diff` can compare them with those of another build */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    // Which input: `real`, the name of another input file or `generated #N`
+    pub input: String,
+    pub answer: String,
+    pub time_ms: f64,
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let lines: Vec<String> = records
+        .iter()
+        .map(|record| {
+            format!(
+                "{{\"day\":{},\"part\":{},\"input\":{},\"answer\":{},\"time_ms\":{:.3}}}",
+                record.day,
+                record.part,
+                json::string(&record.input),
+                json::string(&record.answer),
+                record.time_ms
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+pub fn from_json(text: &str) -> Result<Vec<Record>, String> {
+    json::parse_objects(text)?
+        .iter()
+        .map(|object| {
+            let number = |key| match json::get(object, key) {
+                Some(Value::Number(n)) => Ok(*n),
+                _ => Err(format!("Missing number \"{}\" in a result", key)),
+            };
+            let string = |key| match json::get(object, key) {
+                Some(Value::String(s)) => Ok(s.clone()),
+                _ => Err(format!("Missing string \"{}\" in a result", key)),
+            };
+            Ok(Record {
+                day: number("day")? as u32,
+                part: number("part")? as u32,
+                input: string("input")?,
+                answer: string("answer")?,
+                time_ms: number("time_ms")?,
+            })
+        })
+        .collect()
+}
+
+pub fn read(path: &Path) -> Result<Vec<Record>, String> {
+    from_json(&input::read_file(path)?).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/* The real input and the other ones saved next to it (i.e. `day5.sample.txt`), placeholders and the
+ * expected answers of `extract` excluded */
+fn input_files(dir: &Path, day: u32) -> Vec<(String, String)> {
+    let real = format!("day{}.txt", day);
+    let mut names: Vec<String> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| input::is_input_for(name, day) && !name.contains(".expected."))
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+    // The real input first
+    names.sort_by_key(|name| (*name != real, name.clone()));
+    let mut files = Vec::new();
+    for name in names {
+        if let Ok(content) = input::read_file(dir.join(&name)) {
+            let label = if name == real {
+                "real".to_string()
+            } else {
+                name
+            };
+            files.push((label, content));
+        }
+    }
+    files.retain(|(_, content)| !content.trim().is_empty());
+    files
+}
+
+fn inputs(day: u32, generated: u32, seed: u32) -> Vec<(String, String)> {
+    let mut inputs = input_files(Path::new(input::INPUT_DIR), day);
+    if let Some(generate) = generators::find(day) {
+        let mut rng = Rng::new(seed as u64);
+        for i in 0..generated {
+            inputs.push((format!("generated #{}", i), generate(&mut rng)));
+        }
+    }
+    inputs
+}
+
+pub fn collect(day: Option<u32>, generated: u32, seed: u32) -> Vec<Record> {
+    let mut records = Vec::new();
+    for solution in SOLUTIONS.iter().filter(|s| s.name.is_none()) {
+        if day.is_some() && day != Some(solution.day) {
+            continue;
+        }
+        for (name, content) in inputs(solution.day, generated, seed) {
+            let start = Instant::now();
+            let answer = panic::catch_unwind(|| (solution.solve)(&content))
+                .unwrap_or_else(|_| PANICKED.to_string());
+            records.push(Record {
+                day: solution.day,
+                part: solution.part,
+                input: name,
+                answer,
+                time_ms: start.elapsed().as_secs_f64() * 1000.0,
+            });
+        }
+    }
+    records
+}
+
+/* `collect` for the commands: the panics are recorded as answers, don't print them all. The panic
+ * hook is global, only swap it out when nothing else runs alongside */
+pub fn collect_quietly(day: Option<u32>, generated: u32, seed: u32) -> Vec<Record> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let records = collect(day, generated, seed);
+    panic::set_hook(hook);
+    records
+}
+
+/* The options of `results`, also passed on by `diff` when running another revision */
+pub fn options(args: &[String]) -> Result<(Option<u32>, u32, u32), String> {
+    let day = match args::value(args, "--day") {
+        Some(_) => Some(args::day(args)?),
+        None => None,
+    };
+    let generated = match args::value(args, "--generated") {
+        Some(_) => args::number(args, "--generated")?,
+        None => DEFAULT_GENERATED,
+    };
+    let seed = match args::value(args, "--seed") {
+        Some(_) => args::number(args, "--seed")?,
+        None => DEFAULT_SEED,
+    };
+    Ok((day, generated, seed))
+}
+
+pub fn results(args: &[String]) -> Result<(), String> {
+    let (day, generated, seed) = options(args)?;
+    let json = to_json(&collect_quietly(day, generated, seed));
+    match args::value(args, "--output") {
+        Some(path) => fs::write(path, json).map_err(|e| format!("Cannot write {}: {}", path, e)),
+        None => {
+            print!("{}", json);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        let records = vec![
+            Record {
+                day: 10,
+                part: 2,
+                input: "day10.sample.txt".to_string(),
+                answer: "##..\n..##".to_string(),
+                time_ms: 0.25,
+            },
+            Record {
+                day: 6,
+                part: 1,
+                input: "generated #0".to_string(),
+                answer: PANICKED.to_string(),
+                time_ms: 12.0,
+            },
+        ];
+        assert_eq!(from_json(&to_json(&records)), Ok(records));
+        assert!(from_json("[{\"day\":1}]").is_err());
+    }
+
+    #[test]
+    fn test_collect() {
+        let records = collect(Some(6), 3, 1);
+        let generated: Vec<(u32, &str)> = records
+            .iter()
+            .filter(|r| r.input.starts_with("generated"))
+            .map(|r| (r.part, r.input.as_str()))
+            .collect();
+        assert_eq!(
+            generated,
+            vec![
+                (1, "generated #0"),
+                (1, "generated #1"),
+                (1, "generated #2"),
+                (2, "generated #0"),
+                (2, "generated #1"),
+                (2, "generated #2")
+            ]
+        );
+        // Same seed, same answers
+        let answers = |records: Vec<Record>| -> Vec<String> {
+            records.into_iter().map(|r| r.answer).collect()
+        };
+        assert_eq!(answers(collect(Some(6), 3, 1)), answers(records));
+    }
+
+    #[test]
+    fn test_input_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-results-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, content) in [
+            ("day1.txt", "real"),
+            ("day1.extra.txt", "extra"),
+            ("day1.expected.txt", "24000"),
+            ("day1.empty.txt", ""),
+            ("day11.txt", "other day"),
+        ] {
+            fs::write(dir.join(name), content).unwrap();
+        }
+        assert_eq!(
+            input_files(&dir, 1),
+            vec![
+                ("real".to_string(), "real".to_string()),
+                ("day1.extra.txt".to_string(), "extra".to_string())
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
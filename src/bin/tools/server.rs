@@ -0,0 +1,318 @@
+use crate::{args, json};
+
+use advent_of_code_2022::solutions::{self, SOLUTIONS};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic;
+use std::thread;
+use std::time::Instant;
+
+const DEFAULT_PORT: u32 = 8022;
+const YEAR: u32 = 2022;
+// Puzzle inputs are tens of KB, anything much bigger isn't one
+const MAX_BODY: usize = 4 * 1024 * 1024;
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json::string(message)),
+        }
+    }
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Internal Server Error",
+    }
+}
+
+/* Errors come with the status to answer them with */
+pub fn read_request(stream: &mut TcpStream) -> Result<Request, (u16, String)> {
+    let bad_request = |message: String| (400, message);
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|e| bad_request(e.to_string()))?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => {
+            return Err(bad_request(format!(
+                "Invalid request line: {:?}",
+                request_line
+            )))
+        }
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|e| bad_request(e.to_string()))?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: String::new(),
+    };
+
+    let content_length = match request.header("Content-Length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| bad_request(format!("Invalid Content-Length: {}", value)))?,
+        None => 0,
+    };
+    if content_length > MAX_BODY {
+        return Err((
+            413,
+            format!(
+                "Body of {} bytes, the limit is {}",
+                content_length, MAX_BODY
+            ),
+        ));
+    }
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| bad_request(e.to_string()))?;
+    request.body =
+        String::from_utf8(body).map_err(|_| bad_request("Body is not valid UTF-8".to_string()))?;
+    Ok(request)
+}
+
+fn list_days() -> Response {
+    let mut days: Vec<u32> = SOLUTIONS.iter().map(|solution| solution.day).collect();
+    days.dedup();
+    let entries: Vec<String> = days
+        .iter()
+        .map(|&day| {
+            let parts: Vec<String> = solutions::for_day(day)
+                .map(|solution| solution.part.to_string())
+                .collect();
+            format!("{{\"day\":{},\"parts\":[{}]}}", day, parts.join(","))
+        })
+        .collect();
+    Response {
+        status: 200,
+        body: format!("{{\"year\":{},\"days\":[{}]}}", YEAR, entries.join(",")),
+    }
+}
+
+fn solve(day: u32, part: u32, input: &str) -> Response {
+    let solution = match solutions::find(day, part) {
+        Some(solution) => solution,
+        None => return Response::error(404, "No solution for this day and part"),
+    };
+    let start = Instant::now();
+    let answer = match panic::catch_unwind(|| (solution.solve)(input)) {
+        Ok(answer) => answer,
+        Err(_) => return Response::error(422, "The solution failed on this input"),
+    };
+    let elapsed = start.elapsed();
+    Response {
+        status: 200,
+        body: format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"time_ms\":{:.3}}}",
+            YEAR,
+            day,
+            part,
+            json::string(&answer),
+            elapsed.as_secs_f64() * 1000.0
+        ),
+    }
+}
+
+fn route(request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match segments[..] {
+        ["days"] => match request.method.as_str() {
+            "GET" => list_days(),
+            _ => Response::error(405, "Use GET"),
+        },
+        [year, "day", day, "part", part] => {
+            if request.method != "POST" {
+                return Response::error(405, "Use POST with the puzzle input as body");
+            }
+            match (year.parse::<u32>(), day.parse::<u32>(), part.parse::<u32>()) {
+                (Ok(YEAR), Ok(day), Ok(part)) => solve(day, part, &request.body),
+                (Ok(_), Ok(_), Ok(_)) => Response::error(404, "No solutions for this year"),
+                _ => Response::error(400, "Year, day and part must be numbers"),
+            }
+        }
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+pub fn write_response(stream: &mut TcpStream, status: u16, content_type: &str, body: &str) {
+    let raw = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        status_text(status),
+        content_type,
+        body.len(),
+        body
+    );
+    // Nothing to do when the client went away
+    let _ = stream.write_all(raw.as_bytes());
+}
+
+fn handle(mut stream: TcpStream) {
+    let response = match read_request(&mut stream) {
+        Ok(request) => route(&request),
+        Err((status, e)) => Response::error(status, &e),
+    };
+    write_response(
+        &mut stream,
+        response.status,
+        "application/json",
+        &response.body,
+    );
+}
+
+fn run(listener: TcpListener) {
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || handle(stream));
+    }
+}
+
+pub fn serve(args: &[String]) -> Result<(), String> {
+    let port = match args::value(args, "--port") {
+        Some(_) => args::number(args, "--port")?,
+        None => DEFAULT_PORT,
+    };
+    let address = format!("127.0.0.1:{}", port);
+    let listener =
+        TcpListener::bind(&address).map_err(|e| format!("Cannot bind {}: {}", address, e))?;
+    println!("Listening on http://{}", address);
+    run(listener);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::net::SocketAddr;
+
+    fn start_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || run(listener));
+        address
+    }
+
+    fn request(address: SocketAddr, raw: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse::<u16>().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+        (status, body)
+    }
+
+    fn post(address: SocketAddr, path: &str, body: &str) -> (u16, String) {
+        request(
+            address,
+            &format!(
+                "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+                path,
+                body.len(),
+                body
+            ),
+        )
+    }
+
+    #[test]
+    fn test_solve() {
+        let address = start_server();
+        let (status, body) = post(
+            address,
+            "/2022/day/6/part/2",
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+        );
+        assert_eq!(status, 200);
+        assert!(
+            body.starts_with("{\"year\":2022,\"day\":6,\"part\":2,\"answer\":\"26\",\"time_ms\":"),
+            "{}",
+            body
+        );
+    }
+
+    #[test]
+    fn test_days() {
+        let address = start_server();
+        let (status, body) = request(address, "GET /days HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert_eq!(status, 200);
+        assert!(
+            body.starts_with("{\"year\":2022,\"days\":[{\"day\":1,\"parts\":[1,2]},"),
+            "{}",
+            body
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let address = start_server();
+        assert_eq!(post(address, "/2022/day/42/part/1", "").0, 404);
+        assert_eq!(post(address, "/2021/day/1/part/1", "").0, 404);
+        assert_eq!(post(address, "/2022/day/one/part/1", "").0, 400);
+        assert_eq!(post(address, "/2022/day/1/part/1", "not a number").0, 422);
+        assert_eq!(post(address, "/days", "").0, 405);
+        assert_eq!(
+            request(address, "GET /2022/day/1/part/1 HTTP/1.1\r\n\r\n").0,
+            405
+        );
+        assert_eq!(request(address, "GET /nothing HTTP/1.1\r\n\r\n").0, 404);
+    }
+
+    #[test]
+    fn test_body_too_large() {
+        let address = start_server();
+        let (status, body) = request(
+            address,
+            "POST /2022/day/1/part/1 HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n",
+        );
+        assert_eq!(status, 413);
+        assert!(body.contains("the limit is"), "{}", body);
+    }
+}
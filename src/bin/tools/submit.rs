@@ -0,0 +1,487 @@
+use crate::{args, input};
+
+use advent_of_code_2022::solutions;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const DEFAULT_URL: &str = "https://adventofcode.com";
+const YEAR: u32 = 2022;
+const USER_AGENT: &str = "github.com/Mark90/my-adventofcode-2022-rust submit";
+
+/* History of submitted answers, in the (ignored) input directory next to the puzzle inputs */
+const HISTORY_FILE: &str = "submissions.tsv";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(u64), // seconds left to wait
+    AlreadyCompleted,
+    Unknown(String),
+}
+
+impl Verdict {
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::AlreadyCompleted => "already-completed",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "Wrong answer, too high"),
+            Verdict::TooLow => write!(f, "Wrong answer, too low"),
+            Verdict::Wrong => write!(f, "Wrong answer"),
+            Verdict::RateLimited(seconds) => {
+                write!(
+                    f,
+                    "Answered too recently, wait {}s before trying again",
+                    seconds
+                )
+            }
+            Verdict::AlreadyCompleted => write!(f, "This part was already completed"),
+            Verdict::Unknown(message) => write!(f, "Unrecognized response: {}", message),
+        }
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn parse_wait(message: &str) -> u64 {
+    // i.e. "You have 1m 23s left to wait."
+    let before = match message.split_once(" left to wait") {
+        Some((before, _)) => before,
+        None => return 0,
+    };
+    let mut seconds = 0;
+    for token in before.split_whitespace().rev() {
+        let (number, unit) = token.split_at(token.len().saturating_sub(1));
+        match (number.parse::<u64>(), unit) {
+            (Ok(n), "s") => seconds += n,
+            (Ok(n), "m") => seconds += n * 60,
+            (Ok(n), "h") => seconds += n * 3600,
+            _ => break,
+        }
+    }
+    seconds
+}
+
+fn parse_verdict(html: &str) -> Verdict {
+    // The interesting part is in the <article> element
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let message = strip_tags(article);
+    if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(&message))
+    } else if message.contains("Did you already complete it?") {
+        Verdict::AlreadyCompleted
+    } else if message.contains("That's not the right answer") {
+        if message.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if message.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        Verdict::Unknown(message)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Submission {
+    day: u32,
+    part: u32,
+    verdict: Verdict,
+    answer: String,
+}
+
+fn load_history(path: &Path) -> Vec<Submission> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| match line.split('\t').collect::<Vec<&str>>()[..] {
+            [day, part, verdict, answer] => Some(Submission {
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                verdict: Verdict::from_name(verdict)?,
+                answer: answer.to_string(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn append_history(path: &Path, submission: &Submission) -> Result<(), String> {
+    let mut content = fs::read_to_string(path).unwrap_or_default();
+    content.push_str(&format!(
+        "{}\t{}\t{}\t{}\n",
+        submission.day,
+        submission.part,
+        submission.verdict.name(),
+        submission.answer
+    ));
+    fs::write(path, content).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/* Reason to not submit this answer again, based on earlier submissions */
+fn refuse_reason(history: &[Submission], day: u32, part: u32, answer: &str) -> Option<String> {
+    let numeric = answer.parse::<i64>().ok();
+    for submission in history
+        .iter()
+        .filter(|submission| submission.day == day && submission.part == part)
+    {
+        let previous = submission.answer.parse::<i64>().ok();
+        match (&submission.verdict, numeric, previous) {
+            (Verdict::Correct, _, _) => {
+                return Some(format!("Already solved with {}", submission.answer))
+            }
+            (_, _, _) if submission.answer == answer => {
+                return Some(format!(
+                    "{} was already submitted: {}",
+                    answer, submission.verdict
+                ))
+            }
+            (Verdict::TooHigh, Some(new), Some(old)) if new >= old => {
+                return Some(format!("{} is too high, {} already was", answer, old))
+            }
+            (Verdict::TooLow, Some(new), Some(old)) if new <= old => {
+                return Some(format!("{} is too low, {} already was", answer, old))
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn session() -> Result<String, String> {
+    // Same session token as used by `cargo aoc`, or from the environment
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Ok(session);
+    }
+    let home = env::var("HOME").map_err(|_| "Set AOC_SESSION or HOME".to_string())?;
+    let credentials = PathBuf::from(home).join(".config/cargo-aoc/credentials.toml");
+    let content = fs::read_to_string(&credentials).map_err(|_| {
+        format!(
+            "No session token, set AOC_SESSION or run `cargo aoc credentials -s <token>` ({} not found)",
+            credentials.display()
+        )
+    })?;
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "session")
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .ok_or(format!("No session in {}", credentials.display()))
+}
+
+fn post_answer(
+    base_url: &str,
+    session: &str,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<String, String> {
+    // std has no TLS, leave HTTP to curl
+    let url = format!("{}/{}/day/{}/answer", base_url, YEAR, day);
+    // The session goes in through stdin, arguments are visible to anyone on the machine
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail"])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--header", "@-"])
+        .args(["--data-urlencode", &format!("level={}", part)])
+        .args(["--data-urlencode", &format!("answer={}", answer)])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Could not run curl: {}", e))?;
+    // Dropped after writing, so that curl sees the end of the headers
+    curl.stdin
+        .take()
+        .unwrap()
+        .write_all(format!("Cookie: session={}\n", session).as_bytes())
+        .map_err(|e| format!("Could not pass the session to curl: {}", e))?;
+    let output = curl
+        .wait_with_output()
+        .map_err(|e| format!("Could not run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Posting to {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn submit_answer(
+    base_url: &str,
+    session: &str,
+    history_path: &Path,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict, String> {
+    // Tabs and line breaks would also break the history file
+    if answer.is_empty() || answer.contains(['\t', '\n', '\r']) {
+        return Err(format!(
+            "Can't submit {:?}, pass the answer with --answer",
+            answer
+        ));
+    }
+    if let Some(reason) = refuse_reason(&load_history(history_path), day, part, answer) {
+        return Err(format!("Not submitting: {}", reason));
+    }
+
+    let verdict = parse_verdict(&post_answer(base_url, session, day, part, answer)?);
+    // Only remember definite verdicts, a rate limited answer can be submitted again
+    if Verdict::from_name(verdict.name()).is_some() {
+        append_history(
+            history_path,
+            &Submission {
+                day,
+                part,
+                verdict: verdict.clone(),
+                answer: answer.to_string(),
+            },
+        )?;
+    }
+    Ok(verdict)
+}
+
+pub fn submit(args: &[String]) -> Result<(), String> {
+    let day = args::day(args)?;
+    let part = args::number(args, "--part")?;
+    let answer = match args::value(args, "--answer") {
+        Some(answer) => answer.to_string(),
+        None => {
+            let solution = solutions::find(day, part)
+                .ok_or(format!("No solution for day {} part {}", day, part))?;
+            (solution.solve)(&input::read(day)?)
+        }
+    };
+    let base_url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+    let history_path = Path::new(input::INPUT_DIR).join(HISTORY_FILE);
+
+    println!("Submitting day {} part {}: {}", day, part, answer);
+    let verdict = submit_answer(&base_url, &session()?, &history_path, day, part, &answer)?;
+    println!("{}", verdict);
+    match verdict {
+        Verdict::Correct | Verdict::AlreadyCompleted => Ok(()),
+        _ => Err(format!("Day {} part {} not accepted", day, part)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::server::{read_request, write_response};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            )),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict(&page("That's not the right answer. If you're stuck, ...")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You gave an answer too recently. You have 1m 23s left to wait."
+            )),
+            Verdict::RateLimited(83)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You gave an answer too recently. You have 45s left to wait."
+            )),
+            Verdict::RateLimited(45)
+        );
+        assert_eq!(
+            parse_verdict(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::AlreadyCompleted
+        );
+        assert_eq!(
+            parse_verdict("<html>Puzzle inputs differ by user.</html>"),
+            Verdict::Unknown("Puzzle inputs differ by user.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_refuse_reason() {
+        let submission = |part, verdict, answer: &str| Submission {
+            day: 1,
+            part,
+            verdict,
+            answer: answer.to_string(),
+        };
+        let history = vec![
+            submission(1, Verdict::TooHigh, "500"),
+            submission(1, Verdict::TooLow, "100"),
+            submission(1, Verdict::Wrong, "300"),
+            submission(2, Verdict::Correct, "42"),
+        ];
+        assert!(refuse_reason(&history, 1, 1, "300").is_some());
+        assert!(refuse_reason(&history, 1, 1, "600").is_some());
+        assert!(refuse_reason(&history, 1, 1, "50").is_some());
+        assert!(refuse_reason(&history, 1, 1, "200").is_none());
+        assert!(refuse_reason(&history, 1, 2, "43").is_some());
+        assert!(refuse_reason(&history, 2, 1, "300").is_none());
+    }
+
+    fn history_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-submit-{}-{}.tsv", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let path = history_path("roundtrip");
+        let submission = Submission {
+            day: 7,
+            part: 2,
+            verdict: Verdict::TooLow,
+            answer: "1234".to_string(),
+        };
+        append_history(&path, &submission).unwrap();
+        assert_eq!(load_history(&path), vec![submission]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    /* Stand-in for adventofcode.com answering every submission with the same page */
+    fn start_server(
+        message: &'static str,
+        requests: usize,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut received = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let request = read_request(&mut stream).unwrap();
+                received.push(format!(
+                    "{} {} {} {}",
+                    request.method,
+                    request.path,
+                    request.header("Cookie").unwrap_or(""),
+                    request.body
+                ));
+                write_response(&mut stream, 200, "text/html", &page(message));
+            }
+            received
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_submit_against_stand_in() {
+        if Command::new("curl").arg("--version").output().is_err() {
+            eprintln!("curl not found, skipping");
+            return;
+        }
+        let path = history_path("stand-in");
+
+        let (url, server) = start_server("That's not the right answer; your answer is too low.", 1);
+        let verdict = submit_answer(&url, "secret", &path, 1, 2, "100").unwrap();
+        assert_eq!(verdict, Verdict::TooLow);
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["POST /2022/day/1/answer session=secret level=2&answer=100"]
+        );
+
+        // Refused without contacting the server
+        assert!(submit_answer(&url, "secret", &path, 1, 2, "100").is_err());
+        assert!(submit_answer(&url, "secret", &path, 1, 2, "1\t2").is_err());
+        assert!(submit_answer(&url, "secret", &path, 1, 2, "99").is_err());
+
+        let (url, server) = start_server(
+            "You gave an answer too recently. You have 30s left to wait.",
+            1,
+        );
+        let verdict = submit_answer(&url, "secret", &path, 1, 2, "150").unwrap();
+        assert_eq!(verdict, Verdict::RateLimited(30));
+        server.join().unwrap();
+
+        let (url, server) = start_server("That's the right answer!", 1);
+        let verdict = submit_answer(&url, "secret", &path, 1, 2, "150").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+        server.join().unwrap();
+
+        assert_eq!(load_history(&path).len(), 2);
+        assert!(submit_answer(&url, "secret", &path, 1, 2, "151").is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+use advent_of_code_2022::progress::{CancelToken, Cancelled, Progress};
+use advent_of_code_2022::solutions::{self, ProgressSolution, Solution};
+use std::io::{self, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/* Running a solution for `run --progress` / `run --timeout`. Solutions reporting their progress
+ * (see solutions::PROGRESS_SOLUTIONS) show a bar and stop with what they found so far, the others
+ * are left running on their own thread when they time out */
+
+const BAR_WIDTH: u64 = 40;
+
+struct ProgressBar {
+    shown: bool,
+    drawn: Option<u64>,
+    total: u64,
+}
+
+impl ProgressBar {
+    fn update(&mut self, done: u64, total: u64) {
+        self.total = total;
+        if !self.shown || total == 0 {
+            return;
+        }
+        // Only redraw when the percentage changes, some solvers step a few million times
+        let percent = done * 100 / total;
+        if self.drawn == Some(percent) {
+            return;
+        }
+        self.drawn = Some(percent);
+        eprint!("\r{} {:>3}% ({}/{})", bar(percent), percent, done, total);
+        io::stderr().flush().unwrap();
+    }
+
+    fn finish(&mut self, completed: bool) {
+        // Steps are reported before doing them, so the last one reported isn't all of them
+        if completed {
+            self.update(self.total, self.total);
+        }
+        if self.drawn.is_some() {
+            eprintln!();
+        }
+        self.drawn = None;
+    }
+}
+
+fn bar(percent: u64) -> String {
+    let filled = (percent * BAR_WIDTH / 100) as usize;
+    format!(
+        "[{}{}]",
+        "#".repeat(filled),
+        " ".repeat(BAR_WIDTH as usize - filled)
+    )
+}
+
+fn timed_out(solution: &Solution, timeout: Duration) -> String {
+    format!(
+        "Day {} - Part {}: timed out after {}s",
+        solution.day,
+        solution.part,
+        timeout.as_secs()
+    )
+}
+
+fn solve_with_progress(
+    solution: &ProgressSolution,
+    input: &str,
+    show_progress: bool,
+    token: CancelToken,
+) -> Result<String, Cancelled> {
+    let mut bar = ProgressBar {
+        shown: show_progress,
+        drawn: None,
+        total: 0,
+    };
+    let result = (solution.solve)(
+        input,
+        &mut Progress::new(|done, total| bar.update(done, total), token),
+    );
+    bar.finish(result.is_ok());
+    result
+}
+
+pub fn solve(
+    solution: &Solution,
+    input: &str,
+    show_progress: bool,
+    timeout: Option<Duration>,
+) -> Result<String, String> {
+    if let Some(progressing) = solutions::find_with_progress(solution.day, solution.part) {
+        let token = CancelToken::new();
+        if let Some(timeout) = timeout {
+            let token = token.clone();
+            thread::spawn(move || {
+                thread::sleep(timeout);
+                token.cancel();
+            });
+        }
+        return solve_with_progress(progressing, input, show_progress, token).map_err(
+            |cancelled| format!("{}. {}", timed_out(solution, timeout.unwrap()), cancelled),
+        );
+    }
+
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return Ok((solution.solve)(input)),
+    };
+    // Can't be stopped, stop waiting for it instead
+    let (sender, receiver) = mpsc::channel();
+    let solve = solution.solve;
+    let input = input.to_string();
+    thread::spawn(move || sender.send(solve(&input)));
+    receiver.recv_timeout(timeout).map_err(|e| match e {
+        mpsc::RecvTimeoutError::Timeout => format!(
+            "{} (this solution doesn't report its progress, so there is nothing to show)",
+            timed_out(solution, timeout)
+        ),
+        mpsc::RecvTimeoutError::Disconnected => format!(
+            "Day {} - Part {}: the solution panicked",
+            solution.day, solution.part
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const DAY12: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+    #[test]
+    fn test_bar() {
+        assert_eq!(bar(0), format!("[{}]", " ".repeat(40)));
+        assert_eq!(bar(50), format!("[{}{}]", "#".repeat(20), " ".repeat(20)));
+        assert_eq!(bar(100), format!("[{}]", "#".repeat(40)));
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution = solutions::find(12, 2).unwrap();
+        assert_eq!(solve(solution, DAY12, true, None), Ok("29".to_string()));
+        let timeout = Some(Duration::from_secs(5));
+        assert_eq!(solve(solution, DAY12, false, timeout), Ok("29".to_string()));
+        let solution = solutions::find(12, 1).unwrap();
+        assert_eq!(solve(solution, DAY12, false, timeout), Ok("31".to_string()));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let token = CancelToken::new();
+        token.cancel();
+        let cancelled = solve_with_progress(
+            solutions::find_with_progress(12, 2).unwrap(),
+            DAY12,
+            true,
+            token,
+        )
+        .unwrap_err();
+        assert_eq!((cancelled.done, cancelled.total), (0, 5));
+    }
+}
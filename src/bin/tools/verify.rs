@@ -0,0 +1,102 @@
+use crate::{args, input};
+
+use advent_of_code_2022::generators::{self, Rng};
+use advent_of_code_2022::solutions::{self, SOLUTIONS};
+
+const DEFAULT_GENERATED: u32 = 100;
+const DEFAULT_SEED: u32 = 2022;
+
+/* Days and parts having more than one implementation */
+fn parts_with_variants(day: Option<u32>) -> Vec<(u32, u32)> {
+    let mut parts: Vec<(u32, u32)> = SOLUTIONS
+        .iter()
+        .filter(|solution| solution.name.is_some())
+        .filter(|solution| day.is_none() || day == Some(solution.day))
+        .map(|solution| (solution.day, solution.part))
+        .collect();
+    parts.dedup();
+    parts
+}
+
+fn check(day: u32, part: u32, input: &str, description: &str) -> bool {
+    let disagreeing = solutions::cross_check(day, part, input);
+    if disagreeing.is_empty() {
+        return true;
+    }
+    let expected = (solutions::find(day, part).unwrap().solve)(input);
+    println!(
+        "Day {} - Part {}: MISMATCH on {}, default answered {}",
+        day, part, description, expected
+    );
+    for (name, answer) in disagreeing {
+        println!("\t{} answered {}", name, answer);
+    }
+    false
+}
+
+pub fn verify(args: &[String]) -> Result<(), String> {
+    let day = match args::value(args, "--day") {
+        Some(_) => Some(args::day(args)?),
+        None => None,
+    };
+    let generated = match args::value(args, "--generated") {
+        Some(_) => args::number(args, "--generated")?,
+        None => DEFAULT_GENERATED,
+    };
+    let seed = match args::value(args, "--seed") {
+        Some(_) => args::number(args, "--seed")?,
+        None => DEFAULT_SEED,
+    };
+
+    let mut failures = 0;
+    for (day, part) in parts_with_variants(day) {
+        let names: Vec<&str> = solutions::variants(day, part).map(|s| s.name()).collect();
+        println!("Day {} - Part {}: {}", day, part, names.join(", "));
+
+        // Placeholder input files are empty, skip those
+        match input::read(day) {
+            Ok(content) if !content.is_empty() => {
+                if !check(day, part, &content, "real input") {
+                    failures += 1;
+                }
+            }
+            _ => println!("\tno real input"),
+        }
+
+        match generators::find(day) {
+            Some(generate) => {
+                let mut rng = Rng::new(seed as u64);
+                for i in 0..generated {
+                    let content = generate(&mut rng);
+                    if !check(day, part, &content, &format!("generated input #{}", i)) {
+                        println!("{}", content);
+                        failures += 1;
+                    }
+                }
+            }
+            None => println!("\tno input generator"),
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} input(s) with disagreeing answers", failures));
+    }
+    println!("All variants agree");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parts_with_variants() {
+        assert_eq!(
+            parts_with_variants(None),
+            vec![(6, 1), (6, 2), (14, 2), (20, 1), (20, 2)]
+        );
+        assert_eq!(parts_with_variants(Some(14)), vec![(14, 2)]);
+        assert_eq!(parts_with_variants(Some(1)), vec![]);
+    }
+}
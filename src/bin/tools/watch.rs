@@ -0,0 +1,243 @@
+use crate::{args, input};
+
+use advent_of_code_2022::overflow;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn source_path(day: u32) -> PathBuf {
+    Path::new("src").join(format!("day{:02}.rs", day))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn take_snapshot(day: u32) -> Snapshot {
+    // Modification times of the day's inputs and its source file
+    let mut snapshot = Snapshot::new();
+    if let Ok(entries) = fs::read_dir(input::INPUT_DIR) {
+        for entry in entries.flatten() {
+            let filename = entry.file_name();
+            if input::is_input_for(&filename.to_string_lossy(), day) {
+                let path = entry.path();
+                if let Some(mtime) = modified(&path) {
+                    snapshot.insert(path, mtime);
+                }
+            }
+        }
+    }
+    let source = source_path(day);
+    if let Some(mtime) = modified(&source) {
+        snapshot.insert(source, mtime);
+    }
+    snapshot
+}
+
+fn changed_files(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, mtime)| previous.get(*path) != Some(mtime))
+        .map(|(path, _)| path.clone())
+        .collect();
+    // Removed files count as a change as well
+    changed.extend(
+        previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned(),
+    );
+    changed
+}
+
+fn cargo() -> Command {
+    // Go through cargo so that changes to the source are picked up as well
+    Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+}
+
+fn run_sample_tests(day: u32) -> bool {
+    let filter = format!("day{:02}::", day);
+    match cargo().args(["test", "--quiet", "--lib", &filter]).status() {
+        Ok(status) => status.success(),
+        Err(e) => {
+            eprintln!("Could not run cargo test: {}", e);
+            false
+        }
+    }
+}
+
+fn run_solver(day: u32) -> Result<BTreeMap<u32, String>, String> {
+    let mut command = cargo();
+    command
+        .args(["run", "--quiet", "--release", "--bin", "tools", "--", "run"])
+        .args(["--day", &day.to_string()]);
+    if overflow::is_strict() {
+        command.arg("--strict-overflow");
+    }
+    let output = command
+        .output()
+        .map_err(|e| format!("Could not run solver: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_answers(output: &str) -> BTreeMap<u32, String> {
+    // Parses the output of the `run` command, answers can span multiple lines (i.e. day 10)
+    let mut answers: BTreeMap<u32, String> = BTreeMap::new();
+    let mut current = None;
+    for line in output.lines() {
+        let header = line
+            .strip_prefix("Day ")
+            .and_then(|rest| rest.split_once(" - Part "))
+            .and_then(|(_, rest)| rest.split_once(": "));
+        match header {
+            Some((part, answer)) if part.parse::<u32>().is_ok() => {
+                let part = part.parse::<u32>().unwrap();
+                answers.insert(part, answer.to_string());
+                current = Some(part);
+            }
+            _ => {
+                if let Some(part) = current {
+                    let answer = answers.get_mut(&part).unwrap();
+                    answer.push('\n');
+                    answer.push_str(line);
+                }
+            }
+        }
+    }
+    answers
+}
+
+fn diff_lines(previous: &str, current: &str) -> Vec<String> {
+    let old: Vec<&str> = previous.lines().collect();
+    let new: Vec<&str> = current.lines().collect();
+    let mut diff = Vec::new();
+    for i in 0..old.len().max(new.len()) {
+        match (old.get(i), new.get(i)) {
+            (Some(o), Some(n)) if o == n => diff.push(format!("  {}", n)),
+            (o, n) => {
+                if let Some(o) = o {
+                    diff.push(format!("- {}", o));
+                }
+                if let Some(n) = n {
+                    diff.push(format!("+ {}", n));
+                }
+            }
+        }
+    }
+    diff
+}
+
+fn report(answers: &BTreeMap<u32, String>, previous: &BTreeMap<u32, String>) {
+    for (part, answer) in answers {
+        match previous.get(part) {
+            None => println!("Part {}: {}", part, answer),
+            Some(old) if old == answer => println!("Part {}: {} (unchanged)", part, answer),
+            Some(old) => {
+                println!("Part {}: changed", part);
+                for line in diff_lines(old, answer) {
+                    println!("  {}", line);
+                }
+            }
+        }
+    }
+}
+
+pub fn watch(args: &[String]) -> Result<(), String> {
+    let day = args::day(args)?;
+    if !input::path(day).exists() {
+        return Err(format!(
+            "No input for day {} at {}",
+            day,
+            input::path(day).display()
+        ));
+    }
+    println!(
+        "Watching inputs and source of day {}, press Ctrl-C to stop",
+        day
+    );
+
+    let mut snapshot = Snapshot::new();
+    let mut previous_answers = BTreeMap::new();
+    loop {
+        let current = take_snapshot(day);
+        let changed = changed_files(&snapshot, &current);
+        if !changed.is_empty() {
+            for path in changed.iter() {
+                println!("Changed: {}", path.display());
+            }
+            if run_sample_tests(day) {
+                println!("Sample tests passed");
+            } else {
+                println!("Sample tests FAILED");
+            }
+            match run_solver(day) {
+                Ok(answers) => {
+                    report(&answers, &previous_answers);
+                    previous_answers = answers;
+                }
+                Err(e) => eprintln!("Solver failed:\n{}", e),
+            }
+            // Take a new snapshot so edits made while running aren't missed
+            snapshot = current;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let output = "Day 10 - Part 1: 13140\nDay 10 - Part 2: ##..\n#..#\n";
+        let answers = parse_answers(output);
+        assert_eq!(answers.get(&1).unwrap(), "13140");
+        assert_eq!(answers.get(&2).unwrap(), "##..\n#..#");
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(diff_lines("24000", "24000"), vec!["  24000"]);
+        assert_eq!(diff_lines("24000", "45000"), vec!["- 24000", "+ 45000"]);
+        assert_eq!(
+            diff_lines("##\n..", "##\n.#\n##"),
+            vec!["  ##", "- ..", "+ .#", "+ ##"]
+        );
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(1);
+        let previous = Snapshot::from([
+            (PathBuf::from("day1.txt"), now),
+            (PathBuf::from("day1.sample.txt"), now),
+        ]);
+        let current = Snapshot::from([
+            (PathBuf::from("day1.txt"), later),
+            (PathBuf::from("src/day01.rs"), now),
+        ]);
+        assert_eq!(
+            changed_files(&previous, &current),
+            vec![
+                PathBuf::from("day1.txt"),
+                PathBuf::from("src/day01.rs"),
+                PathBuf::from("day1.sample.txt"),
+            ]
+        );
+        assert!(changed_files(&current, &current).is_empty());
+    }
+}
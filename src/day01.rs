@@ -1,43 +1,76 @@
-use aoc_runner_derive::aoc;
-
-fn parse(content: &str) -> Vec<i32> {
-    let mut sums: Vec<i32> = Vec::new();
-    let mut current_sum: i32 = 0;
-    for _line in content.lines() {
-        let line = _line;
-        if line.len() == 0 {
-            sums.push(current_sum);
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{overflow, utils};
+use std::io::BufRead;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 1,
+    title: "Calorie Counting",
+    tags: &[Tag::Parsing],
+    complexity: "O(n), keeping only the top three sums",
+};
+
+fn for_each_sum<R: BufRead, F: FnMut(i64)>(reader: R, mut f: F) {
+    // Calls f with the calories of every elf, without keeping them around
+    // Sums are i64 so that generated inputs with lots of items don't overflow
+    let mut current_sum: i64 = 0;
+    for line in utils::lines(reader) {
+        if line.is_empty() {
+            f(overflow::fits::<i32, _>(current_sum, "calories of an elf"));
             current_sum = 0;
         } else {
-            let new_sum = line.parse::<i32>().unwrap();
-            current_sum += new_sum;
+            let new_sum = line.parse::<i64>().unwrap();
+            current_sum = overflow::add(current_sum, new_sum, "calories of an elf");
         }
     }
     if current_sum > 0 {
-        sums.push(current_sum);
+        f(overflow::fits::<i32, _>(current_sum, "calories of an elf"));
     }
-    return sums;
+}
+
+#[aoc_generator(day1)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> i64 {
+    // Keep the three highest sums, in descending order
+    let mut top_sums = [0i64; 3];
+    for_each_sum(reader, |sum| {
+        if sum > top_sums[2] {
+            top_sums[2] = sum;
+            top_sums.sort_by(|x, y| y.cmp(x));
+        }
+    });
+    overflow::fits::<i32, _>(
+        overflow::sum(top_sums, "calories of the top three elves"),
+        "calories of the top three elves",
+    )
 }
 
 #[aoc(day1, part2)]
-fn part2(content: &str) -> i32 {
-    let mut sums: Vec<i32> = parse(content);
-    sums.sort_by(|x, y| y.cmp(x));
-    let part2_sum = sums[0] + sums[1] + sums[2];
-    return part2_sum;
+pub(crate) fn part2(content: &str) -> i64 {
+    part2_reader(content.as_bytes())
+}
+
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> i64 {
+    let mut max_sum = 0;
+    for_each_sum(reader, |sum| max_sum = max_sum.max(sum));
+    max_sum
 }
 
 #[aoc(day1, part1)]
-fn part1(content: &str) -> i32 {
-    let sums: Vec<i32> = parse(content);
-    let max_sum = sums.iter().max_by(|x, y| x.cmp(y)).unwrap();
-    return *max_sum;
+pub(crate) fn part1(content: &str) -> i64 {
+    part1_reader(content.as_bytes())
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "1000
 2000
@@ -63,4 +96,30 @@ mod tests {
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 45000);
     }
+
+    #[test]
+    fn test_overflow() {
+        // Two elves carrying more than fits in an i32
+        let input = "2000000000\n2000000000\n\n1";
+        assert_eq!(part1(input), 4000000000);
+        assert_eq!(part2(input), 4000000001);
+    }
+
+    #[test]
+    fn test_reader() {
+        // Small buffer so that lines are split over several reads
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            24000
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            45000
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(1, INPUT);
+    }
 }
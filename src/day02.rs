@@ -1,4 +1,15 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
+use std::io::BufRead;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 2,
+    title: "Rock Paper Scissors",
+    tags: &[Tag::Parsing],
+    complexity: "O(n)",
+};
 
 fn get_score(choice: &str) -> i32 {
     match choice {
@@ -23,10 +34,9 @@ fn game_outcome_score(their_score: i32, our_score: i32) -> i32 {
     return 0;
 }
 
-#[aoc(day2, part1)]
-fn part1(content: &str) -> i32 {
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> i32 {
     let mut total_score = 0i32;
-    for line in content.lines() {
+    for line in utils::lines(reader) {
         let str_vec = line.split(' ').collect::<Vec<&str>>();
         let [their_shape, our_shape] = <[&str; 2]>::try_from(str_vec).ok().unwrap();
         let [their_score, our_score] = [get_score(their_shape), get_score(our_shape)];
@@ -35,10 +45,19 @@ fn part1(content: &str) -> i32 {
     return total_score; // 14297
 }
 
-#[aoc(day2, part2)]
-fn part2(content: &str) -> i32 {
+#[aoc_generator(day2)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day2, part1)]
+pub(crate) fn part1(content: &str) -> i32 {
+    part1_reader(content.as_bytes())
+}
+
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> i32 {
     let mut score = 0i32;
-    for line in content.lines() {
+    for line in utils::lines(reader) {
         let str_vec = line.split(' ').collect::<Vec<&str>>();
         let [their_shape, expected_outcome] = <[&str; 2]>::try_from(str_vec).ok().unwrap();
         let their_score = get_score(their_shape);
@@ -65,10 +84,17 @@ fn part2(content: &str) -> i32 {
     return score; // 10498
 }
 
+#[aoc(day2, part2)]
+pub(crate) fn part2(content: &str) -> i32 {
+    part2_reader(content.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "A Y
 B X
@@ -83,4 +109,21 @@ C Z";
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 12);
     }
+
+    #[test]
+    fn test_reader() {
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            15
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            12
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(2, INPUT);
+    }
 }
@@ -1,6 +1,16 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
 use std::collections::HashSet;
+use std::io::BufRead;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 3,
+    title: "Rucksack Reorganization",
+    tags: &[Tag::Strings],
+    complexity: "O(n) in the number of items",
+};
 
 fn to_priority(c: char) -> i32 {
     if 'a' <= c && c <= 'z' {
@@ -10,13 +20,10 @@ fn to_priority(c: char) -> i32 {
     }
 }
 
-#[aoc(day3, part1)]
-fn part1(content: &str) -> i32 {
-    content
-        .lines()
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> i32 {
+    utils::lines(reader)
         .map(|line| {
-            let l = line.to_string();
-            let (sack1, sack2) = l.split_at(l.len() / 2);
+            let (sack1, sack2) = line.split_at(line.len() / 2);
             let s1: HashSet<char> = sack1.chars().collect::<HashSet<char>>();
             let s2: HashSet<char> = sack2.chars().collect::<HashSet<char>>();
             to_priority(*s1.intersection(&s2).next().unwrap())
@@ -25,11 +32,18 @@ fn part1(content: &str) -> i32 {
     // 8139
 }
 
-#[aoc(day3, part2)]
-fn part2(content: &str) -> i32 {
-    let mut sacks = content
-        .lines()
-        .map(|line| line.chars().collect::<HashSet<char>>());
+#[aoc_generator(day3)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day3, part1)]
+pub(crate) fn part1(content: &str) -> i32 {
+    part1_reader(content.as_bytes())
+}
+
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> i32 {
+    let mut sacks = utils::lines(reader).map(|line| line.chars().collect::<HashSet<char>>());
 
     let mut result = 0i32;
     let mut group = sacks.next().unwrap();
@@ -52,10 +66,17 @@ fn part2(content: &str) -> i32 {
     // 2668
 }
 
+#[aoc(day3, part2)]
+pub(crate) fn part2(content: &str) -> i32 {
+    part2_reader(content.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -73,4 +94,21 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 70);
     }
+
+    #[test]
+    fn test_reader() {
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            157
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            70
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(3, INPUT);
+    }
 }
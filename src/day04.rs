@@ -1,11 +1,19 @@
-use aoc_runner_derive::aoc;
-
-fn section_tuple(inp: &str) -> (i32, i32) {
-    let sections: Vec<&str> = inp.split('-').collect();
-    (
-        sections[0].parse::<i32>().unwrap(),
-        sections[1].parse::<i32>().unwrap(),
-    )
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
+use std::io::BufRead;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 4,
+    title: "Camp Cleanup",
+    tags: &[Tag::Parsing, Tag::Intervals],
+    complexity: "O(n)",
+};
+
+fn section_pair(line: &str) -> ((i32, i32), (i32, i32)) {
+    let [left_start, left_end, right_start, right_end] = parsing::extract_array(line).unwrap();
+    ((left_start, left_end), (right_start, right_end))
 }
 
 fn contains(left: (i32, i32), right: (i32, i32)) -> bool {
@@ -13,20 +21,24 @@ fn contains(left: (i32, i32), right: (i32, i32)) -> bool {
     right.0 >= left.0 && right.1 <= left.1
 }
 
-#[aoc(day4, part1)]
-fn part1(content: &str) -> i32 {
-    content
-        .lines()
-        .map(|line| line.split(',').collect())
-        .map(|sections: Vec<&str>| {
-            let left = section_tuple(sections[0]);
-            let right = section_tuple(sections[1]);
-            (contains(left, right) || contains(right, left)) as i32
-        })
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> i32 {
+    utils::lines(reader)
+        .map(|line| section_pair(&line))
+        .map(|(left, right)| (contains(left, right) || contains(right, left)) as i32)
         .sum()
     // 453
 }
 
+#[aoc_generator(day4)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day4, part1)]
+pub(crate) fn part1(content: &str) -> i32 {
+    part1_reader(content.as_bytes())
+}
+
 fn overlap(left: (i32, i32), right: (i32, i32)) -> bool {
     /* Do left and right overlap? */
     contains(left, right)
@@ -35,24 +47,25 @@ fn overlap(left: (i32, i32), right: (i32, i32)) -> bool {
         || (left.1 > right.1 && (left.0 >= right.0 && left.0 <= right.1))
 }
 
-#[aoc(day4, part2)]
-fn part2(content: &str) -> i32 {
-    content
-        .lines()
-        .map(|line| line.split(',').collect())
-        .map(|sections: Vec<&str>| {
-            let left = section_tuple(sections[0]);
-            let right = section_tuple(sections[1]);
-            (overlap(left, right)) as i32
-        })
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> i32 {
+    utils::lines(reader)
+        .map(|line| section_pair(&line))
+        .map(|(left, right)| overlap(left, right) as i32)
         .sum()
     // 919
 }
 
+#[aoc(day4, part2)]
+pub(crate) fn part2(content: &str) -> i32 {
+    part2_reader(content.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "2-4,6-8
 2-3,4-5
@@ -71,4 +84,21 @@ mod tests {
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 4);
     }
+
+    #[test]
+    fn test_reader() {
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            2
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            4
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(4, INPUT);
+    }
 }
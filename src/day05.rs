@@ -1,9 +1,20 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
 use std::collections::HashMap;
 
-fn parse_stacks(input: &str) -> HashMap<&str, Vec<&str>> {
-    let mut stacks: HashMap<&str, Vec<&str>> = HashMap::new();
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 5,
+    title: "Supply Stacks",
+    tags: &[Tag::Parsing, Tag::Simulation],
+    complexity: "O(moves × crates moved)",
+};
+
+type Stacks<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+fn parse_stacks(input: &str) -> Stacks<'_> {
+    let mut stacks: Stacks = HashMap::new();
     let drawing = input.lines();
     let stack_numbers = input.lines().last().unwrap();
     let mut column = 1;
@@ -28,7 +39,12 @@ fn parse_stacks(input: &str) -> HashMap<&str, Vec<&str>> {
 }
 
 fn solve_it(content: &str, part1: bool) -> String {
-    let input_sections = content.split("\n\n").collect::<Vec<_>>();
+    solve_observed(content, part1, |_| {})
+}
+
+fn solve_observed<F: FnMut(&Stacks)>(content: &str, part1: bool, mut observe: F) -> String {
+    // Calls `observe` with the stacks after every move
+    let input_sections = parsing::blocks(content);
     assert_eq!(input_sections.len(), 2);
 
     let mut stacks = parse_stacks(input_sections[0]);
@@ -36,12 +52,10 @@ fn solve_it(content: &str, part1: bool) -> String {
     let instructions = input_sections[1].lines();
 
     for instruction in instructions {
-        let s = instruction.split(" ").collect::<Vec<&str>>();
-        assert_eq!(s.len(), 6);
-
-        let num_crates = s[1].to_string().parse::<usize>().unwrap();
-        let from_stack = s[3];
-        let to_stack = s[5];
+        let fields = parsing::scan("move {} from {} to {}", instruction).unwrap();
+        let num_crates = parsing::number::<usize>(fields[0]).unwrap();
+        let from_stack = fields[1];
+        let to_stack = fields[2];
 
         // Get the stack to move from
         let stack: &mut Vec<&str> = stacks.get_mut(from_stack).unwrap();
@@ -57,6 +71,7 @@ fn solve_it(content: &str, part1: bool) -> String {
                 entry.extend(crates.iter())
             }
         });
+        observe(&stacks);
     }
 
     // TODO figure out the funcional approach, should be something like this
@@ -71,14 +86,19 @@ fn solve_it(content: &str, part1: bool) -> String {
     result
 }
 
+#[aoc_generator(day5)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day5, part1)]
-fn part1(content: &str) -> String {
+pub(crate) fn part1(content: &str) -> String {
     solve_it(content, true)
     // SHMSDGZVC
 }
 
 #[aoc(day5, part2)]
-fn part2(content: &str) -> String {
+pub(crate) fn part2(content: &str) -> String {
     solve_it(content, false)
     // VRZGHDFBQ
 }
@@ -87,6 +107,9 @@ fn part2(content: &str) -> String {
 mod tests {
 
     use super::*;
+    use crate::snapshot;
+    use crate::solutions;
+
     const INPUT: &str = "    [D]
 [N] [C]
 [Z] [M] [P]
@@ -107,4 +130,51 @@ move 1 from 1 to 2
     fn test_part_2() {
         assert_eq!(part2(&INPUT), "MCD");
     }
+
+    fn draw(stacks: &Stacks) -> String {
+        // Same drawing as in the puzzle
+        let height = stacks.values().map(|stack| stack.len()).max().unwrap();
+        let mut lines = Vec::new();
+        for level in (0..height).rev() {
+            let line: Vec<String> = (1..=stacks.len())
+                .map(|number| match stacks[&number.to_string()[..]].get(level) {
+                    Some(crate_) => format!("[{}]", crate_),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            lines.push(line.join(" ").trim_end().to_string());
+        }
+        let numbers: Vec<String> = (1..=stacks.len())
+            .map(|number| format!(" {} ", number))
+            .collect();
+        lines.push(numbers.join(" ").trim_end().to_string());
+        lines.join("\n")
+    }
+
+    fn moves(part1: bool) -> String {
+        let mut states = Vec::new();
+        solve_observed(INPUT, part1, |stacks| {
+            states.push(format!(
+                "== After move {} ==\n{}\n",
+                states.len() + 1,
+                draw(stacks)
+            ))
+        });
+        states.join("\n")
+    }
+
+    #[test]
+    fn test_snapshot_part_1() {
+        snapshot::assert_snapshot("day05_part1", &moves(true));
+    }
+
+    #[test]
+    fn test_snapshot_part_2() {
+        snapshot::assert_snapshot("day05_part2", &moves(false));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(5, INPUT);
+    }
 }
@@ -1,42 +1,106 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
 
-use std::collections::HashSet;
+use crate::generators::Rng;
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
 
-fn get_start_marker(content: &str, marker_size: usize) -> i32 {
-    let chars = content
-        .lines()
-        .next()
-        .unwrap()
-        .chars()
-        .collect::<Vec<char>>();
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 6,
+    title: "Tuning Trouble",
+    tags: &[Tag::Strings],
+    complexity: "O(n·k) for markers of k characters, O(n) with the bitmask",
+};
 
-    for position in (marker_size - 1)..chars.len() {
-        let window: HashSet<&char> = chars[position - (marker_size - 1)..position + 1]
-            .into_iter()
-            .collect();
+fn get_start_marker<R: BufRead>(reader: R, marker_size: usize) -> usize {
+    // Reads the datastream byte by byte, only keeping the last `marker_size` characters
+    let mut window: VecDeque<u8> = VecDeque::with_capacity(marker_size);
+    let datastream = reader
+        .bytes()
+        .map(|c| c.unwrap())
+        .take_while(|&c| c != b'\n');
+
+    for (position, c) in datastream.enumerate() {
         if window.len() == marker_size {
-            return (position as i32) + 1;
+            window.pop_front();
+        }
+        window.push_back(c);
+        if window.iter().collect::<HashSet<&u8>>().len() == marker_size {
+            return position + 1;
         }
     }
     unreachable!("No solution");
 }
 
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> usize {
+    get_start_marker(reader, 4)
+}
+
+#[aoc_generator(day6)]
+#[aoc_generator(day6, part1, Bitmask)]
+#[aoc_generator(day6, part2, Bitmask)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day6, part1)]
-fn part1(content: &str) -> i32 {
-    get_start_marker(content, 4)
+pub(crate) fn part1(content: &str) -> usize {
+    part1_reader(content.as_bytes())
     // 1538
 }
 
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> usize {
+    get_start_marker(reader, 14)
+}
+
 #[aoc(day6, part2)]
-fn part2(content: &str) -> i32 {
-    get_start_marker(content, 14)
+pub(crate) fn part2(content: &str) -> usize {
+    part2_reader(content.as_bytes())
     // 2315
 }
 
+fn get_start_marker_bitmask(content: &str, marker_size: usize) -> usize {
+    // Same as get_start_marker, but with one bit per letter instead of a HashSet for every window
+    let chars = content.lines().next().unwrap().as_bytes();
+
+    for position in (marker_size - 1)..chars.len() {
+        let window = chars[position - (marker_size - 1)..position + 1]
+            .iter()
+            .fold(0u32, |mask, c| mask | 1 << (c - b'a'));
+        if window.count_ones() as usize == marker_size {
+            return position + 1;
+        }
+    }
+    unreachable!("No solution");
+}
+
+#[aoc(day6, part1, Bitmask)]
+pub(crate) fn part1_bitmask(content: &str) -> usize {
+    get_start_marker_bitmask(content, 4)
+}
+
+#[aoc(day6, part2, Bitmask)]
+pub(crate) fn part2_bitmask(content: &str) -> usize {
+    get_start_marker_bitmask(content, 14)
+}
+
+pub(crate) fn generate(rng: &mut Rng) -> String {
+    // Few distinct letters so that markers don't show up right away, ending with a marker to always have a solution
+    let length = rng.range(20, 2000);
+    let mut datastream: String = (0..length)
+        .map(|_| (b'a' + rng.range(0, 15) as u8) as char)
+        .collect();
+    datastream.push_str("abcdefghijklmn\n");
+    datastream
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
 
@@ -49,4 +113,27 @@ mod tests {
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 26);
     }
+
+    #[test]
+    fn test_reader() {
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            11
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            26
+        );
+    }
+
+    #[test]
+    fn test_bitmask() {
+        assert_eq!(part1_bitmask(INPUT), 11);
+        assert_eq!(part2_bitmask(INPUT), 26);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(6, INPUT);
+    }
 }
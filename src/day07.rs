@@ -1,10 +1,20 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{overflow, utils};
 
 use Node::*;
 
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 7,
+    title: "No Space Left On Device",
+    tags: &[Tag::Parsing, Tag::Recursion],
+    complexity: "O(n) to build the tree, O(d log d) to sort the d directory sizes",
+};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Node {
-    File(String, u32),
+    File(String, u64),
     Directory(String, Vec<Node>),
 }
 
@@ -13,7 +23,7 @@ impl Node {
         Directory(dirname.to_string(), vec![])
     }
 
-    fn add_file(&mut self, filename: &str, size: u32) {
+    fn add_file(&mut self, filename: &str, size: u64) {
         if let Directory(_dirname, ref mut wrapped_vector) = self {
             wrapped_vector.push(File(filename.to_string(), size));
         }
@@ -24,14 +34,18 @@ impl Node {
         }
     }
 
-    fn get_directory_sizes(&self, dir_sizes: &mut Vec<(String, u32)>) -> u32 {
+    fn get_directory_sizes(&self, dir_sizes: &mut Vec<(String, u64)>) -> u64 {
         // Recurses through nodes, storing every directory's calculated size in `dir_sizes`
         match self {
             Directory(_dirname, wrapped_vector) => {
-                let total_size = wrapped_vector
+                let sizes: Vec<u64> = wrapped_vector
                     .iter()
                     .map(|node| node.get_directory_sizes(dir_sizes))
-                    .sum();
+                    .collect();
+                let total_size = overflow::fits::<u32, _>(
+                    overflow::sum(sizes, "directory size"),
+                    "directory size",
+                );
                 dir_sizes.push((_dirname.to_string(), total_size));
                 total_size
             }
@@ -63,7 +77,7 @@ fn enter_dir(parent: &mut Node, output_iter: &mut std::iter::Skip<std::str::Line
                             // Sanity check
                             unreachable!("Parsed ls output while not listing!?")
                         }
-                        parent.add_file(filename, filesize.parse::<u32>().unwrap());
+                        parent.add_file(filename, filesize.parse::<u64>().unwrap());
                     }
                     _ => unreachable!("Unhandled line format"),
                 }
@@ -81,47 +95,55 @@ fn parse_terminal_output(content: &str) -> Node {
     root
 }
 
+#[aoc_generator(day7)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day7, part1)]
-fn part1(content: &str) -> u32 {
+pub(crate) fn part1(content: &str) -> u64 {
     // Parse the file structure
     let root = parse_terminal_output(content);
 
     // Gather directory sizes
-    let mut dir_sizes: Vec<(String, u32)> = Vec::new();
+    let mut dir_sizes: Vec<(String, u64)> = Vec::new();
     root.get_directory_sizes(&mut dir_sizes);
 
     // Return sum of directory sizes <= 100_000
-    dir_sizes
+    let small_sizes = dir_sizes
         .iter()
         .map(|(_dirname, dirsize)| *dirsize)
-        .filter(|size| *size <= 100_000)
-        .sum::<u32>()
+        .filter(|size| *size <= 100_000);
+    overflow::sum(small_sizes, "sum of small directories")
     // 919137
 }
 
 #[aoc(day7, part2)]
-fn part2(content: &str) -> u32 {
-    let total_diskspace: u32 = 70000000;
-    let required_free_diskspace: u32 = 30000000;
+pub(crate) fn part2(content: &str) -> u64 {
+    let total_diskspace: u64 = 70000000;
+    let required_free_diskspace: u64 = 30000000;
 
     // Parse the file structure
     let root = parse_terminal_output(content);
 
     // Gather directory sizes
-    let mut dir_sizes: Vec<(String, u32)> = Vec::new();
+    let mut dir_sizes: Vec<(String, u64)> = Vec::new();
     root.get_directory_sizes(&mut dir_sizes);
 
     // Determine used diskspace
     let root_size = dir_sizes
         .iter()
-        .filter(|(dirname, _dirsize)| dirname == "/")
-        .next()
+        .find(|(dirname, _dirsize)| dirname == "/")
         .unwrap();
 
-    let current_free_diskspace = total_diskspace - root_size.1;
+    let current_free_diskspace = overflow::sub(total_diskspace, root_size.1, "free diskspace");
 
     // Determine the diskspace to be freed, and the smallest directory to fulfil this
-    let extra_space_needed = required_free_diskspace - current_free_diskspace;
+    let extra_space_needed = overflow::sub(
+        required_free_diskspace,
+        current_free_diskspace,
+        "diskspace to be freed",
+    );
     dir_sizes.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
 
     let directory_to_remove = dir_sizes
@@ -138,6 +160,7 @@ fn part2(content: &str) -> u32 {
 mod tests {
 
     use super::*;
+    use crate::solutions;
 
     const INPUT: &str = "$ cd /
 $ ls
@@ -172,4 +195,26 @@ $ ls
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 24933642);
     }
+
+    const LARGE_INPUT: &str = "$ cd /
+$ ls
+4294967000 a
+1000 b";
+
+    #[test]
+    fn test_overflow() {
+        // Would wrap around to a small directory of 704 in a u32
+        assert_eq!(part1(LARGE_INPUT), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow in free diskspace")]
+    fn test_overflow_disk_full() {
+        part2(LARGE_INPUT);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(7, INPUT);
+    }
 }
@@ -1,7 +1,17 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
 
 use std::collections::{HashMap, HashSet};
 
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 8,
+    title: "Treetop Tree House",
+    tags: &[Tag::Grid],
+    complexity: "O(n²) sweeps of an n×n grid for part 1, O(n³) viewing distances for part 2",
+};
+
 fn check_row(
     y: usize,
     gridsize: usize,
@@ -61,8 +71,13 @@ fn check_column(
     }
 }
 
+#[aoc_generator(day8)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day8, part1)]
-fn part1(content: &str) -> i32 {
+pub(crate) fn part1(content: &str) -> i32 {
     let mut all_trees: HashMap<(i32, i32), u32> = HashMap::new();
     let mut visible_trees: HashSet<(i32, i32)> = HashSet::new();
     let gridsize = content.lines().next().unwrap().len();
@@ -156,7 +171,7 @@ fn scenic_score(all_trees: &HashMap<(i32, i32), u32>, gridsize: i32, x: i32, y:
 }
 
 #[aoc(day8, part2)]
-fn part2(content: &str) -> i32 {
+pub(crate) fn part2(content: &str) -> i32 {
     let mut trees: HashMap<(i32, i32), u32> = HashMap::new();
     let gridsize = content.lines().next().unwrap().len() as i32;
 
@@ -180,6 +195,7 @@ fn part2(content: &str) -> i32 {
 mod tests {
 
     use super::*;
+    use crate::solutions;
 
     const INPUT: &str = "30373
 25512
@@ -197,4 +213,9 @@ mod tests {
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 8);
     }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(8, INPUT);
+    }
 }
@@ -1,5 +1,16 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
 use std::collections::HashSet;
+use std::io::BufRead;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 9,
+    title: "Rope Bridge",
+    tags: &[Tag::Grid, Tag::Simulation],
+    complexity: "O(steps × knots)",
+};
 
 const PART2_TAILSIZE: usize = 9;
 
@@ -43,15 +54,14 @@ fn move_right(head: &mut Coord) {
     head.x += 1;
 }
 
-#[aoc(day9, part1)]
-fn part1(content: &str) -> i32 {
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> i32 {
     let mut head = Coord { x: 0, y: 0 };
     let mut tail = Coord { x: 0, y: 0 };
 
     let mut visited: HashSet<(i32, i32)> = HashSet::new();
     visited.insert(tail.as_tuple());
-    for line in content.lines() {
-        let mut split = line.split(" ");
+    for line in utils::lines(reader) {
+        let mut split = line.split(' ');
         let direction = split.next().unwrap();
         let steps = split.next().unwrap().parse::<i32>().unwrap();
         let move_head = match direction {
@@ -72,21 +82,30 @@ fn part1(content: &str) -> i32 {
     // 6494
 }
 
+#[aoc_generator(day9)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day9, part1)]
+pub(crate) fn part1(content: &str) -> i32 {
+    part1_reader(content.as_bytes())
+}
+
 fn move_tail_to_tail(tails: &mut [Coord; PART2_TAILSIZE], tail_index: usize) {
     let head = tails[tail_index - 1];
     let mut tail = &mut tails[tail_index];
     move_tail_to_head(&head, &mut tail);
 }
 
-#[aoc(day9, part2)]
-fn part2(content: &str) -> i32 {
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> i32 {
     let mut head = Coord { x: 0, y: 0 };
     let mut tails = [Coord { x: 0, y: 0 }; PART2_TAILSIZE];
 
     let mut visited: HashSet<(i32, i32)> = HashSet::new();
     visited.insert(tails.last().unwrap().as_tuple());
-    for line in content.lines() {
-        let mut split = line.split(" ");
+    for line in utils::lines(reader) {
+        let mut split = line.split(' ');
         let direction = split.next().unwrap();
         let steps = split.next().unwrap().parse::<i32>().unwrap();
         let move_head = match direction {
@@ -110,10 +129,17 @@ fn part2(content: &str) -> i32 {
     // 2691
 }
 
+#[aoc(day9, part2)]
+pub(crate) fn part2(content: &str) -> i32 {
+    part2_reader(content.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "R 4
 U 4
@@ -187,4 +213,21 @@ R 2
         let larger_sample = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n";
         assert_eq!(part2(&larger_sample), 36);
     }
+
+    #[test]
+    fn test_reader() {
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            13
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            1
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(9, INPUT);
+    }
 }
@@ -1,4 +1,15 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
+use std::io::BufRead;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 10,
+    title: "Cathode-Ray Tube",
+    tags: &[Tag::Parsing, Tag::Simulation],
+    complexity: "O(cycles)",
+};
 
 #[derive(Debug)]
 enum Instruction {
@@ -6,16 +17,16 @@ enum Instruction {
     ADDX(i32),
 }
 
-#[aoc(day10, part1)]
-fn part1(content: &str) -> i32 {
-    let mut instructions =
-        content
-            .lines()
-            .map(|line| match line.split(" ").collect::<Vec<&str>>()[..] {
-                ["noop"] => Instruction::NOOP,
-                ["addx", something] => Instruction::ADDX(something.parse::<i32>().unwrap()),
-                _ => unreachable!("imp"),
-            });
+fn parse_instructions<R: BufRead>(reader: R) -> impl Iterator<Item = Instruction> {
+    utils::lines(reader).map(|line| match line.split(' ').collect::<Vec<&str>>()[..] {
+        ["noop"] => Instruction::NOOP,
+        ["addx", something] => Instruction::ADDX(something.parse::<i32>().unwrap()),
+        _ => unreachable!("imp"),
+    })
+}
+
+pub(crate) fn part1_reader<R: BufRead>(reader: R) -> i32 {
+    let mut instructions = parse_instructions(reader);
 
     let check_cycles: [i32; 6] = [20, 60, 100, 140, 180, 220];
     let mut sums = Vec::new();
@@ -49,16 +60,18 @@ fn part1(content: &str) -> i32 {
     // 15260
 }
 
-#[aoc(day10, part2)]
-fn part2(content: &str) -> String {
-    let mut instructions =
-        content
-            .lines()
-            .map(|line| match line.split(" ").collect::<Vec<&str>>()[..] {
-                ["noop"] => Instruction::NOOP,
-                ["addx", something] => Instruction::ADDX(something.parse::<i32>().unwrap()),
-                _ => unreachable!("imp"),
-            });
+#[aoc_generator(day10)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day10, part1)]
+pub(crate) fn part1(content: &str) -> i32 {
+    part1_reader(content.as_bytes())
+}
+
+pub(crate) fn part2_reader<R: BufRead>(reader: R) -> String {
+    let mut instructions = parse_instructions(reader);
     let mut x = 1;
     let mut cycle = 0;
     let mut pixels: Vec<char> = Vec::new();
@@ -98,10 +111,17 @@ fn part2(content: &str) -> String {
     // PGHFGLUG
 }
 
+#[aoc(day10, part2)]
+pub(crate) fn part2(content: &str) -> String {
+    part2_reader(content.as_bytes())
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::solutions;
+    use std::io::BufReader;
 
     const INPUT: &str = "addx 15
 addx -11
@@ -266,4 +286,21 @@ noop
 #######.......#######.......#######.....";
         assert_eq!(part2(&INPUT), expected);
     }
+
+    #[test]
+    fn test_reader() {
+        assert_eq!(
+            part1_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            13140
+        );
+        assert_eq!(
+            part2_reader(BufReader::with_capacity(3, INPUT.as_bytes())),
+            part2(INPUT)
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(10, INPUT);
+    }
 }
@@ -1,7 +1,19 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::metadata::{Metadata, Tag};
+use crate::overflow;
+use crate::parsing::{self, ParseError};
+use crate::progress::{Cancelled, Progress};
+use crate::utils;
 use std::fmt;
 
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 11,
+    title: "Monkey in the Middle",
+    tags: &[Tag::Parsing, Tag::Simulation, Tag::Math],
+    complexity: "O(rounds × items), worry levels are kept modulo the product of the tests",
+};
+
 #[derive(Debug)]
 enum Operator {
     MULTIPLY,
@@ -11,14 +23,15 @@ enum Operator {
 #[derive(Debug)]
 enum Target {
     SELF,
-    STATIC(u64),
+    STATIC(u128),
 }
 
+// Worry levels are u128, squaring them overflows a u64 long before the puzzle answers do
 struct Monkey {
-    inventory: Vec<u64>,
+    inventory: Vec<u128>,
     operation_target: Target,
     operation_operator: Operator,
-    test_value: u64,
+    test_value: u128,
     test_true: u64,
     test_false: u64,
     items_inspected: u64,
@@ -35,15 +48,18 @@ impl fmt::Display for Monkey {
 }
 
 impl Monkey {
-    fn inspect_item(&self, old: u64) -> u64 {
-        match (&self.operation_target, &self.operation_operator) {
-            (Target::SELF, Operator::ADD) => old + old,
-            (Target::SELF, Operator::MULTIPLY) => old * old,
-            (Target::STATIC(value), Operator::ADD) => old + value,
-            (Target::STATIC(value), Operator::MULTIPLY) => old * value,
-        }
+    fn inspect_item(&self, old: u128) -> u128 {
+        let new = match (&self.operation_target, &self.operation_operator) {
+            (Target::SELF, Operator::ADD) => overflow::add(old, old, "worry level"),
+            (Target::SELF, Operator::MULTIPLY) => overflow::mul(old, old, "worry level"),
+            (Target::STATIC(value), Operator::ADD) => overflow::add(old, *value, "worry level"),
+            (Target::STATIC(value), Operator::MULTIPLY) => {
+                overflow::mul(old, *value, "worry level")
+            }
+        };
+        overflow::fits::<u64, _>(new, "worry level")
     }
-    fn throw_item_to(&self, item_worry_level: u64) -> u64 {
+    fn throw_item_to(&self, item_worry_level: u128) -> u64 {
         if item_worry_level % self.test_value == 0 {
             return self.test_true;
         }
@@ -51,75 +67,62 @@ impl Monkey {
     }
 }
 
-fn get_last_int(line: &str) -> u64 {
-    line.split_ascii_whitespace()
-        .last()
-        .unwrap()
-        .parse::<u64>()
-        .unwrap()
+fn parse_monkey(block: &str) -> Result<Monkey, ParseError> {
+    let lines: Vec<&str> = block.lines().collect();
+    // Missing lines show up as not matching their pattern
+    let line = |index: usize| lines.get(index).copied().unwrap_or("");
+
+    parsing::scan("Monkey {}:", line(0))?;
+    let items = parsing::extract_ints(parsing::scan("Starting items: {}", line(1))?[0])?;
+    let operation = parsing::scan("Operation: new = old {} {}", line(2))?;
+    let divide_by = parsing::number(parsing::scan("Test: divisible by {}", line(3))?[0])?;
+    let test_true = parsing::number(parsing::scan("If true: throw to monkey {}", line(4))?[0])?;
+    let test_false = parsing::number(parsing::scan("If false: throw to monkey {}", line(5))?[0])?;
+
+    let operation_operator = match operation[0] {
+        "*" => Operator::MULTIPLY,
+        "+" => Operator::ADD,
+        _ => {
+            return Err(ParseError::Mismatch {
+                pattern: "Operation: new = old {*,+} {}".to_string(),
+                line: line(2).to_string(),
+            })
+        }
+    };
+    let operation_target = match operation[1] {
+        "old" => Target::SELF,
+        value => Target::STATIC(parsing::number(value)?),
+    };
+    Ok(Monkey {
+        inventory: items,
+        operation_operator,
+        operation_target,
+        test_value: divide_by,
+        test_true,
+        test_false,
+        items_inspected: 0,
+    })
 }
 
 fn get_monkeys_from_input(content: &str) -> Vec<Monkey> {
-    let mut monkeys = Vec::new();
-    for lines in content.split("\n\n") {
-        let mut split_lines = lines.lines().skip(1);
-        let starting = split_lines
-            .next()
-            .unwrap()
-            .split(": ")
-            .skip(1)
-            .next()
-            .unwrap();
-        let items: Vec<u64> = starting
-            .split(", ")
-            .map(|c| c.parse::<u64>().unwrap())
-            .collect();
-        let line_operation: Vec<&str> = split_lines
-            .next()
-            .unwrap()
-            .split_ascii_whitespace()
-            .collect();
-
-        let operator = line_operation[line_operation.len() - 2];
-        let target = line_operation[line_operation.len() - 1];
-        let divide_by = get_last_int(split_lines.next().unwrap());
-        let test_true = get_last_int(split_lines.next().unwrap());
-        let test_false = get_last_int(split_lines.next().unwrap());
-
-        let operation_operator = match operator {
-            "*" => Operator::MULTIPLY,
-            "+" => Operator::ADD,
-            _ => unreachable!("imp"),
-        };
-        let operation_target = match target {
-            "old" => Target::SELF,
-            value => Target::STATIC(value.parse::<u64>().unwrap()),
-        };
-        monkeys.push(Monkey {
-            inventory: items,
-            operation_operator: operation_operator,
-            operation_target: operation_target,
-            test_value: divide_by,
-            test_true: test_true,
-            test_false: test_false,
-            items_inspected: 0,
-        })
-    }
-    monkeys
+    parsing::blocks(content)
+        .into_iter()
+        .map(|block| parse_monkey(block).unwrap())
+        .collect()
 }
 
-fn monkey_turn(monkey: &mut Monkey, combined_modulo: u64) -> Vec<(u64, u64)> {
+fn monkey_turn(monkey: &mut Monkey, combined_modulo: u128) -> Vec<(u64, u128)> {
     // Keep track of monkeyids/items thrown by this monkey
     // (cannot modify `monkeys` here due to borrowing restrictions)
-    let mut items_thrown: Vec<(u64, u64)> = Vec::new();
+    let mut items_thrown: Vec<(u64, u128)> = Vec::new();
     for &item_wl_old in monkey.inventory.iter() {
         let mut item_wl_new = monkey.inspect_item(item_wl_old);
         if combined_modulo == 0 {
             // part 1
-            item_wl_new = (item_wl_new as f32 / 3_f32).floor() as u64;
+            item_wl_new /= 3;
         } else {
             // part 2: apply product of all test values as modulo, because that won't change the 'throw item to' division
-            item_wl_new = item_wl_new % combined_modulo;
+            item_wl_new %= combined_modulo;
         }
         let monkey_id = monkey.throw_item_to(item_wl_new);
         items_thrown.push((monkey_id, item_wl_new));
@@ -130,7 +133,7 @@ fn monkey_turn(monkey: &mut Monkey, combined_modulo: u64) -> Vec<(u64, u64)> {
     items_thrown
 }
 
-fn monkey_business(monkeys: &mut [Monkey], combined_modulo: u64) {
+fn monkey_business(monkeys: &mut [Monkey], combined_modulo: u128) {
     for this_monkey in 0..monkeys.len() {
         let mut monkey = &mut monkeys[this_monkey];
         let items_thrown = monkey_turn(&mut monkey, combined_modulo);
@@ -140,42 +143,59 @@ fn monkey_business(monkeys: &mut [Monkey], combined_modulo: u64) {
     }
 }
 
-#[aoc(day11, part1)]
-fn part1(content: &str) -> u64 {
-    let mut monkeys = get_monkeys_from_input(content);
-    let mut round = 0;
-    loop {
-        round += 1;
-        monkey_business(&mut monkeys, 0);
-        if round == 20 {
-            break;
-        }
+fn play_rounds<F: FnMut(u32, &[Monkey]) -> Result<(), Cancelled>>(
+    monkeys: &mut [Monkey],
+    rounds: u32,
+    combined_modulo: u128,
+    mut observe: F,
+) -> Result<(), Cancelled> {
+    // Calls `observe` with the monkeys after every round, which can stop playing
+    for round in 1..=rounds {
+        monkey_business(monkeys, combined_modulo);
+        observe(round, monkeys)?;
     }
+    Ok(())
+}
 
+fn monkey_business_level(monkeys: &[Monkey]) -> u64 {
     let mut item_inspections: Vec<u64> = monkeys.iter().map(|m| m.items_inspected).collect();
     item_inspections.sort();
     let highest = &item_inspections[item_inspections.len() - 2..];
-    highest[0] as u64 * highest[1] as u64
+    overflow::mul(highest[0], highest[1], "monkey business")
+}
+
+#[aoc_generator(day11)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day11, part1)]
+pub(crate) fn part1(content: &str) -> u64 {
+    let mut monkeys = get_monkeys_from_input(content);
+    play_rounds(&mut monkeys, 20, 0, |_, _| Ok(())).unwrap();
+    monkey_business_level(&monkeys)
     // 58322
 }
 
 #[aoc(day11, part2)]
-fn part2(content: &str) -> u64 {
-    let mut monkeys = get_monkeys_from_input(content);
-    let combined_modulo: u64 = monkeys.iter().map(|m| m.test_value).product();
-    let mut round = 0;
-    loop {
-        round += 1;
-        monkey_business(&mut monkeys, combined_modulo);
-        if round == 10000 {
-            break;
-        }
-    }
+pub(crate) fn part2(content: &str) -> u64 {
+    part2_with_progress(content, &mut Progress::none()).unwrap()
+}
 
-    let mut item_inspections: Vec<u64> = monkeys.iter().map(|m| m.items_inspected).collect();
-    item_inspections.sort();
-    let highest = &item_inspections[item_inspections.len() - 2..];
-    highest[0] as u64 * highest[1] as u64
+pub(crate) fn part2_with_progress(
+    content: &str,
+    progress: &mut Progress,
+) -> Result<u64, Cancelled> {
+    let mut monkeys = get_monkeys_from_input(content);
+    let combined_modulo = monkeys.iter().fold(1u128, |product, m| {
+        overflow::mul(product, m.test_value, "product of the test values")
+    });
+    play_rounds(&mut monkeys, 10000, combined_modulo, |round, monkeys| {
+        progress.step(round as u64, 10000, || {
+            format!("monkey business {} so far", monkey_business_level(monkeys))
+        })
+    })?;
+    Ok(monkey_business_level(&monkeys))
     // 13937702909
 }
 
@@ -183,6 +203,9 @@ fn part2(content: &str) -> u64 {
 mod tests {
 
     use super::*;
+    use crate::progress::CancelToken;
+    use crate::snapshot;
+    use crate::solutions;
 
     const INPUT: &str = "Monkey 0:
   Starting items: 79, 98
@@ -222,4 +245,113 @@ Monkey 3:
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 2713310158u64);
     }
+
+    #[test]
+    fn test_part_2_cancelled() {
+        // Cancelled from the report of round 20, the puzzle shows the inspections after that round
+        let token = CancelToken::new();
+        let canceller = token.clone();
+        let mut progress = Progress::new(
+            move |done, _| {
+                if done == 20 {
+                    canceller.cancel();
+                }
+            },
+            token,
+        );
+        assert_eq!(
+            part2_with_progress(INPUT, &mut progress),
+            Err(Cancelled {
+                done: 20,
+                total: 10000,
+                diagnostics: "monkey business 10197 so far".to_string()
+            })
+        );
+    }
+
+    fn rounds(rounds: u32, part2: bool, shown: &[u32]) -> String {
+        let mut monkeys = get_monkeys_from_input(INPUT);
+        let combined_modulo = if part2 {
+            monkeys.iter().map(|m| m.test_value).product()
+        } else {
+            0
+        };
+        let mut states = Vec::new();
+        play_rounds(&mut monkeys, rounds, combined_modulo, |round, monkeys| {
+            if shown.is_empty() || shown.contains(&round) {
+                let lines: Vec<String> = monkeys
+                    .iter()
+                    .enumerate()
+                    .map(|(i, monkey)| format!("Monkey {}: {}", i, monkey))
+                    .collect();
+                states.push(format!(
+                    "== After round {} ==\n{}\n",
+                    round,
+                    lines.join("\n")
+                ));
+            }
+            Ok(())
+        })
+        .unwrap();
+        states.join("\n")
+    }
+
+    #[test]
+    fn test_snapshot_part_1() {
+        snapshot::assert_snapshot("day11_part1", &rounds(20, false, &[]));
+    }
+
+    #[test]
+    fn test_snapshot_part_2() {
+        // The rounds shown in the puzzle
+        let shown = [
+            1, 20, 1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000, 9000, 10000,
+        ];
+        snapshot::assert_snapshot("day11_part2", &rounds(10000, true, &shown));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let block = INPUT.split("\n\n").next().unwrap();
+        assert_eq!(
+            parse_monkey(&block.replace(" * ", " / ")).err(),
+            Some(ParseError::Mismatch {
+                pattern: "Operation: new = old {*,+} {}".to_string(),
+                line: "  Operation: new = old / 19".to_string()
+            })
+        );
+        // Missing lines
+        assert_eq!(
+            parse_monkey(&block[..block.find("  Test").unwrap()]).err(),
+            Some(ParseError::Mismatch {
+                pattern: "Test: divisible by {}".to_string(),
+                line: "".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Squaring the first item overflows a u64 before dividing by 3
+        let input = "Monkey 0:
+  Starting items: 5000000000
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 3
+  Operation: new = old + 1
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+";
+        assert_eq!(part1(input), 40);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(11, INPUT);
+    }
 }
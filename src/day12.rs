@@ -1,8 +1,19 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::progress::{Cancelled, Progress};
+use crate::utils;
 
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
-fn get_neighbor(position: &(i32, i32), direction: i32) -> (i32, i32) {
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 12,
+    title: "Hill Climbing Algorithm",
+    tags: &[Tag::Grid, Tag::Pathfinding],
+    complexity: "O(V log V) A* from S, repeated from every 'a' square for part 2",
+};
+
+pub(crate) fn get_neighbor(position: &(i32, i32), direction: i32) -> (i32, i32) {
     match direction {
         0 => (position.0, position.1 - 1), // up
         1 => (position.0 + 1, position.1), // right
@@ -112,8 +123,13 @@ fn a_star<H: Fn((i32, i32)) -> i32>(
     None
 }
 
+#[aoc_generator(day12)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day12, part1)]
-fn part1(content: &str) -> i32 {
+pub(crate) fn part1(content: &str) -> i32 {
     // Parse the input
     let mut square_heights: HashMap<(i32, i32), i32> = HashMap::new();
     let mut start = (0, 0);
@@ -149,7 +165,14 @@ fn part1(content: &str) -> i32 {
 }
 
 #[aoc(day12, part2)]
-fn part2(content: &str) -> i32 {
+pub(crate) fn part2(content: &str) -> i32 {
+    part2_with_progress(content, &mut Progress::none()).unwrap()
+}
+
+pub(crate) fn part2_with_progress(
+    content: &str,
+    progress: &mut Progress,
+) -> Result<i32, Cancelled> {
     // Parse the input, this time keeping an array of all possible start squares
     let mut square_heights: HashMap<(i32, i32), i32> = HashMap::new();
     let mut start_squares = Vec::new();
@@ -181,7 +204,11 @@ fn part2(content: &str) -> i32 {
     // Use A* to find shortest path from each start square to E, return the lowest
     // Not very efficient but still completes in a few seconds on a 5 year old laptop with an i5
     let mut overall_shortest_path_length = width * height;
-    for start in start_squares {
+    let total = start_squares.len() as u64;
+    for (i, start) in start_squares.into_iter().enumerate() {
+        progress.step(i as u64, total, || {
+            format!("shortest path so far {}", overall_shortest_path_length)
+        })?;
         match a_star(start, goal, heuristic, width, height, &square_heights) {
             Some(shortest_path) => {
                 overall_shortest_path_length =
@@ -191,7 +218,7 @@ fn part2(content: &str) -> i32 {
         }
     }
 
-    overall_shortest_path_length
+    Ok(overall_shortest_path_length)
     // 418
 }
 
@@ -199,6 +226,8 @@ fn part2(content: &str) -> i32 {
 mod tests {
 
     use super::*;
+    use crate::progress::CancelToken;
+    use crate::solutions;
     const INPUT: &str = "Sabqponm
 abcryxxl
 accszExk
@@ -215,4 +244,31 @@ abdefghi
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 29);
     }
+
+    #[test]
+    fn test_part_2_cancelled() {
+        let token = CancelToken::new();
+        let canceller = token.clone();
+        let mut progress = Progress::new(
+            move |done, _| {
+                if done == 3 {
+                    canceller.cancel();
+                }
+            },
+            token,
+        );
+        assert_eq!(
+            part2_with_progress(INPUT, &mut progress),
+            Err(Cancelled {
+                done: 3,
+                total: 5,
+                diagnostics: "shortest path so far 30".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(12, INPUT);
+    }
 }
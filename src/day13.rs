@@ -1,9 +1,18 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
 use std::cmp::Ordering;
 
 use Item::*;
 
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 13,
+    title: "Distress Signal",
+    tags: &[Tag::Parsing, Tag::Recursion],
+    complexity: "O(n log n) comparisons of nested packets",
+};
+
 #[derive(Debug, Clone, PartialEq)]
 enum Item {
     Number(u32),
@@ -87,11 +96,16 @@ fn parse_packet(raw_packet_line: &str) -> Vec<Item> {
     result
 }
 
+#[aoc_generator(day13)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day13, part1)]
-fn part1(content: &str) -> i32 {
+pub(crate) fn part1(content: &str) -> i32 {
     // parse the input into pairs
     let mut pairs: Vec<(Vec<Item>, Vec<Item>)> = Vec::new();
-    let string_pairs = content.split("\n\n");
+    let string_pairs = parsing::blocks(content);
     for string_pair in string_pairs {
         let parsed: (Vec<Item>, Vec<Item>) = parse_pair(string_pair);
         pairs.push(parsed);
@@ -113,10 +127,10 @@ fn part1(content: &str) -> i32 {
 }
 
 #[aoc(day13, part2)]
-fn part2(content: &str) -> i32 {
+pub(crate) fn part2(content: &str) -> i32 {
     // Parse the input packets
     let mut packets: Vec<Vec<Item>> = Vec::new();
-    let string_pairs = content.split("\n\n");
+    let string_pairs = parsing::blocks(content);
     for string_pair in string_pairs {
         let parsed: (Vec<Item>, Vec<Item>) = parse_pair(string_pair);
         packets.push(parsed.0);
@@ -203,6 +217,7 @@ mod tests {
     use std::vec;
 
     use super::*;
+    use crate::solutions;
 
     const INPUT: &str = "[1,1,3,1,1]
 [1,1,5,1,1]
@@ -396,4 +411,9 @@ mod tests {
         let actual_result = parse_packet(input);
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(13, INPUT);
+    }
 }
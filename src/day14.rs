@@ -1,14 +1,25 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::generators::Rng;
+use crate::metadata::{Metadata, Tag};
+use crate::parsing;
+use crate::utils;
 use std::collections::HashSet;
 
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 14,
+    title: "Regolith Reservoir",
+    tags: &[Tag::Grid, Tag::Simulation],
+    complexity: "O(grains × depth of the cave)",
+};
+
 fn parse_grid(content: &str) -> HashSet<(i32, i32)> {
     let mut grid: HashSet<(i32, i32)> = HashSet::new();
     for blockline in content.lines() {
         let coords: Vec<(i32, i32)> = blockline
             .split(" -> ")
-            .map(|e| e.split_once(",").unwrap())
-            .map(|(x, y)| (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap()))
+            .map(|e| parsing::extract_array(e).unwrap())
+            .map(|[x, y]| (x, y))
             .collect();
         let mut prev = coords.first().unwrap();
         for coord in coords.iter().skip(1) {
@@ -27,8 +38,20 @@ fn parse_grid(content: &str) -> HashSet<(i32, i32)> {
     grid
 }
 
+#[aoc_generator(day14)]
+#[aoc_generator(day14, part2, Grid)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day14, part1)]
-fn part1(content: &str) -> i32 {
+pub(crate) fn part1(content: &str) -> i32 {
+    pour_sand(content, |_, _| {})
+    // 799
+}
+
+fn pour_sand<F: FnMut(i32, &HashSet<(i32, i32)>)>(content: &str, mut observe: F) -> i32 {
+    // Calls `observe` with the number of settled blocks and the grid every time a block settles
     let mut grid = parse_grid(content);
 
     let grid_ymax = grid.iter().map(|(_, y)| *y).max().unwrap();
@@ -66,6 +89,7 @@ fn part1(content: &str) -> i32 {
                 settled = true;
                 grid.insert(sand);
                 blocks_settled += 1;
+                observe(blocks_settled, &grid);
                 break;
             }
 
@@ -78,11 +102,10 @@ fn part1(content: &str) -> i32 {
     }
 
     blocks_settled
-    // 799
 }
 
 #[aoc(day14, part2)]
-fn part2(content: &str) -> i32 {
+pub(crate) fn part2(content: &str) -> i32 {
     // Takes a few seconds to complete because of the HashSet grid.
     // In hindsight using a set of coordinates is a bad idea; while it provides instant lookups, there are also
     // many inserts happening and those are very slow.
@@ -130,10 +153,72 @@ fn part2(content: &str) -> i32 {
     // 29076
 }
 
+#[aoc(day14, part2, Grid)]
+pub(crate) fn part2_grid(content: &str) -> i32 {
+    // The 2-D array approach from the comment in part2
+    let rocks = parse_grid(content);
+    let grid_ymax = rocks.iter().map(|(_, y)| *y).max().unwrap();
+
+    // Sand can't spread further than the floor depth to either side of the origin
+    let floor = grid_ymax + 2;
+    let xmin = 500 - floor;
+    let width = (2 * floor + 1) as usize;
+    let mut grid = vec![vec![false; width]; floor as usize];
+    for (x, y) in rocks {
+        if x >= xmin && x < xmin + width as i32 {
+            grid[y as usize][(x - xmin) as usize] = true;
+        }
+    }
+
+    let sand_origin = ((500 - xmin) as usize, 0usize);
+    let mut blocks_settled = 0;
+    while !grid[sand_origin.1][sand_origin.0] {
+        let mut sand = sand_origin;
+        loop {
+            let (x, y) = sand;
+            if y + 1 == floor as usize {
+                break;
+            }
+            // down, down+left, down+right
+            match [x, x - 1, x + 1].iter().find(|&&newx| !grid[y + 1][newx]) {
+                Some(&newx) => sand = (newx, y + 1),
+                None => break,
+            }
+        }
+        grid[sand.1][sand.0] = true;
+        blocks_settled += 1;
+    }
+
+    blocks_settled
+}
+
+pub(crate) fn generate(rng: &mut Rng) -> String {
+    // Random horizontal/vertical rock paths, keeping the spot below the sand origin free
+    let mut lines = Vec::new();
+    for _ in 0..rng.range(1, 10) {
+        let mut x = rng.range(480, 520);
+        let mut y = rng.range(3, 30);
+        let mut path = vec![format!("{},{}", x, y)];
+        for _ in 0..rng.range(1, 4) {
+            if rng.range(0, 1) == 0 {
+                x = (x + rng.range(-8, 8)).clamp(470, 530);
+            } else {
+                y = (y + rng.range(-8, 8)).clamp(3, 40);
+            }
+            path.push(format!("{},{}", x, y));
+        }
+        lines.push(path.join(" -> "));
+    }
+    lines.join("\n") + "\n"
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::snapshot;
+    use crate::solutions;
+
     const INPUT: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9
 ";
@@ -147,4 +232,52 @@ mod tests {
     fn test_part_2() {
         assert_eq!(part2(&INPUT), 93);
     }
+
+    fn draw(rocks: &HashSet<(i32, i32)>, grid: &HashSet<(i32, i32)>) -> String {
+        // Same drawing as in the puzzle, including the sand source
+        let xmin = grid.iter().map(|(x, _)| *x).min().unwrap().min(500);
+        let xmax = grid.iter().map(|(x, _)| *x).max().unwrap().max(500);
+        let ymax = grid.iter().map(|(_, y)| *y).max().unwrap();
+        let mut lines = Vec::new();
+        for y in 0..=ymax {
+            let line: String = (xmin..=xmax)
+                .map(|x| match (x, y) {
+                    (500, 0) if !grid.contains(&(x, y)) => '+',
+                    position if rocks.contains(&position) => '#',
+                    position if grid.contains(&position) => 'o',
+                    _ => '.',
+                })
+                .collect();
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_snapshot_part_1() {
+        // The grains shown in the puzzle
+        let shown = [1, 2, 5, 22, 24];
+        let rocks = parse_grid(INPUT);
+        let mut states = Vec::new();
+        pour_sand(INPUT, |settled, grid| {
+            if shown.contains(&settled) {
+                states.push(format!(
+                    "== After {} grains ==\n{}\n",
+                    settled,
+                    draw(&rocks, grid)
+                ));
+            }
+        });
+        snapshot::assert_snapshot("day14_part1", &states.join("\n"));
+    }
+
+    #[test]
+    fn test_part_2_grid() {
+        assert_eq!(part2_grid(INPUT), 93);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(14, INPUT);
+    }
 }
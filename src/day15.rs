@@ -1,24 +1,50 @@
-use aoc_runner_derive::aoc;
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::progress::{Cancelled, Progress};
+use crate::{overflow, parsing, utils};
 use std::collections::HashSet;
 
-fn mhdist(a: (i32, i32), b: (i32, i32)) -> i32 {
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 15,
+    title: "Beacon Exclusion Zone",
+    tags: &[Tag::Intervals],
+    complexity: "O(s log s) per row for s sensors, over up to 4000000 rows for part 2",
+};
+
+// Coordinates are i64, distances between far apart i32 positions don't fit an i32
+fn mhdist(a: (i64, i64), b: (i64, i64)) -> i64 {
     (a.0 - b.0).abs() + (a.1 - b.1).abs()
 }
 
+fn parse_report(line: &str) -> [i64; 4] {
+    // Sensor x, y and its closest beacon x, y
+    let coordinates: [i64; 4] = parsing::extract_array(line).unwrap();
+    coordinates.map(|coordinate| overflow::fits::<i32, _>(coordinate, "coordinate"))
+}
+
+fn tuning_frequency(beacon: (i64, i64)) -> i64 {
+    overflow::add(
+        overflow::mul(beacon.0, 4000000, "tuning frequency"),
+        beacon.1,
+        "tuning frequency",
+    )
+}
+
+#[aoc_generator(day15)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
 #[aoc(day15, part1)]
-fn part1(content: &str) -> u32 {
-    let mut sensors: Vec<(i32, i32, i32)> = Vec::new();
-    let mut beacons: HashSet<(i32, i32)> = HashSet::new();
+pub(crate) fn part1(content: &str) -> u64 {
+    let mut sensors: Vec<(i64, i64, i64)> = Vec::new();
+    let mut beacons: HashSet<(i64, i64)> = HashSet::new();
 
-    let mut xmin = i32::MAX;
-    let mut xmax = i32::MIN;
+    let mut xmin = i64::MAX;
+    let mut xmax = i64::MIN;
     for line in content.lines() {
-        let normalized = line.replace(",", "").replace(":", "").replace("=", " ");
-        let lineparts: Vec<&str> = normalized.split_whitespace().collect();
-        let sx = lineparts[3].parse::<i32>().unwrap();
-        let sy = lineparts[5].parse::<i32>().unwrap();
-        let bx = lineparts[11].parse::<i32>().unwrap();
-        let by = lineparts[13].parse::<i32>().unwrap();
+        let [sx, sy, bx, by] = parse_report(line);
         let dist_s_to_b = mhdist((sx, sy), (bx, by));
         xmin = xmin.min(sx - dist_s_to_b);
         xmax = xmax.max(sx + dist_s_to_b);
@@ -27,7 +53,7 @@ fn part1(content: &str) -> u32 {
     }
 
     let y = if xmax > 100 { 2000000 } else { 10 };
-    let mut positions_without_beacon = 0u32;
+    let mut positions_without_beacon = 0u64;
     for x in xmin..=xmax {
         let position = (x, y);
         if beacons.contains(&position) {
@@ -51,18 +77,20 @@ fn part1(content: &str) -> u32 {
 }
 
 #[aoc(day15, part2)]
-fn part2(content: &str) -> i64 {
+pub(crate) fn part2(content: &str) -> i64 {
+    part2_with_progress(content, &mut Progress::none()).unwrap()
+}
+
+pub(crate) fn part2_with_progress(
+    content: &str,
+    progress: &mut Progress,
+) -> Result<i64, Cancelled> {
     // Build list of Sensors x,y,r where r is their 'radius' (MH distance to nearest beacon)
-    let mut sensors: Vec<(i32, i32, i32)> = Vec::new();
+    let mut sensors: Vec<(i64, i64, i64)> = Vec::new();
 
-    let mut xmax = i32::MIN;
+    let mut xmax = i64::MIN;
     for line in content.lines() {
-        let normalized = line.replace(",", "").replace(":", "").replace("=", " ");
-        let lineparts: Vec<&str> = normalized.split_whitespace().collect();
-        let sx = lineparts[3].parse::<i32>().unwrap();
-        let sy = lineparts[5].parse::<i32>().unwrap();
-        let bx = lineparts[11].parse::<i32>().unwrap();
-        let by = lineparts[13].parse::<i32>().unwrap();
+        let [sx, sy, bx, by] = parse_report(line);
         let dist_s_to_b = mhdist((sx, sy), (bx, by));
         xmax = xmax.max(sx + dist_s_to_b);
         sensors.push((sx, sy, dist_s_to_b));
@@ -72,12 +100,15 @@ fn part2(content: &str) -> i64 {
     // Determine gridsize from input
     let gridsize = if xmax > 100 { 4000000 } else { 20 };
 
-    let mut possible_beacons: HashSet<(i32, i32)> = HashSet::new();
+    let mut possible_beacons: HashSet<(i64, i64)> = HashSet::new();
 
     // Traverse grid diagonally
     for i in 0..=gridsize {
+        progress.step(i as u64, gridsize as u64 + 1, || {
+            format!("{} possible beacons so far", possible_beacons.len())
+        })?;
         // Check the Y-axis; for each sensor find out the range it covers on this axis (if any)
-        let mut sensor_yranges: Vec<(i32, i32)> = sensors
+        let mut sensor_yranges: Vec<(i64, i64)> = sensors
             .iter()
             .filter_map(|sensor| get_yrange(sensor, i))
             .collect();
@@ -88,16 +119,16 @@ fn part2(content: &str) -> i64 {
         for j in 1..sensor_yranges.len() {
             if (sensor_yranges[j].0 - sensor_yranges[j - 1].1) > 1 {
                 // Found a gap to previous range
-                let possible_beacon = (i as i32, sensor_yranges[j - 1].1 + 1);
+                let possible_beacon = (i, sensor_yranges[j - 1].1 + 1);
                 if possible_beacons.contains(&possible_beacon) {
-                    return (possible_beacon.0 as i64) * 4000000_i64 + (possible_beacon.1 as i64);
+                    return Ok(tuning_frequency(possible_beacon));
                 }
                 possible_beacons.insert(possible_beacon);
             }
         }
 
         // Check the X-axis; for each sensor find out the range it covers on this axis (if any)
-        let mut sensor_xranges: Vec<(i32, i32)> = sensors
+        let mut sensor_xranges: Vec<(i64, i64)> = sensors
             .iter()
             .filter_map(|sensor| get_xrange(sensor, i))
             .collect();
@@ -108,9 +139,9 @@ fn part2(content: &str) -> i64 {
         for j in 1..sensor_xranges.len() {
             if (sensor_xranges[j].0 - sensor_xranges[j - 1].1) > 1 {
                 // Found a gap to previous range
-                let possible_beacon = (sensor_xranges[j - 1].1 + 1, i as i32);
+                let possible_beacon = (sensor_xranges[j - 1].1 + 1, i);
                 if possible_beacons.contains(&possible_beacon) {
-                    return (possible_beacon.0 as i64) * 4000000_i64 + (possible_beacon.1 as i64);
+                    return Ok(tuning_frequency(possible_beacon));
                 }
                 possible_beacons.insert(possible_beacon);
             }
@@ -129,12 +160,12 @@ where
     move |item| (f(i, item), i += 1).0
 }
 
-fn remove_contained_ranges(ranges: &mut Vec<(i32, i32)>) {
+fn remove_contained_ranges(ranges: &mut Vec<(i64, i64)>) {
     // Assume the ranges are sorted on first lower, then upper boundary,
     // filter out all ranges which are partially/fully contained in the previous range.
     // Honestly not sure if this is 100% correct, but it seems to work \o/
-    let mut last_upper_bound = i32::MIN;
-    ranges.retain(with_index(|index, range: &(i32, i32)| {
+    let mut last_upper_bound = i64::MIN;
+    ranges.retain(with_index(|index, range: &(i64, i64)| {
         if index == 0 || range.1 > last_upper_bound {
             last_upper_bound = range.1;
             true
@@ -144,7 +175,7 @@ fn remove_contained_ranges(ranges: &mut Vec<(i32, i32)>) {
     }));
 }
 
-fn get_yrange(sensor: &(i32, i32, i32), x_axis: i32) -> Option<(i32, i32)> {
+fn get_yrange(sensor: &(i64, i64, i64), x_axis: i64) -> Option<(i64, i64)> {
     let (sensor_x, sensor_y, radius) = sensor;
 
     let delta = radius - (x_axis - sensor_x).abs();
@@ -154,7 +185,7 @@ fn get_yrange(sensor: &(i32, i32, i32), x_axis: i32) -> Option<(i32, i32)> {
     Some((sensor_y - delta, sensor_y + delta))
 }
 
-fn get_xrange(sensor: &(i32, i32, i32), y: i32) -> Option<(i32, i32)> {
+fn get_xrange(sensor: &(i64, i64, i64), y: i64) -> Option<(i64, i64)> {
     let (sensor_x, sensor_y, radius) = sensor;
 
     let delta = radius - (y - sensor_y).abs();
@@ -168,6 +199,8 @@ fn get_xrange(sensor: &(i32, i32, i32), y: i32) -> Option<(i32, i32)> {
 mod tests {
 
     use super::*;
+    use crate::progress::CancelToken;
+    use crate::solutions;
 
     const INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
@@ -195,6 +228,30 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3
         assert_eq!(part2(&INPUT), 56000011);
     }
 
+    #[test]
+    fn test_part_2_cancelled() {
+        let token = CancelToken::new();
+        token.cancel();
+        let cancelled = part2_with_progress(INPUT, &mut Progress::new(|_, _| {}, token));
+        assert_eq!(
+            cancelled,
+            Err(Cancelled {
+                done: 0,
+                total: 21,
+                diagnostics: "0 possible beacons so far".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            mhdist((-2_000_000_000, 0), (2_000_000_000, 1)),
+            4_000_000_001
+        );
+        assert_eq!(tuning_frequency((4_000_000, 4_000_000)), 16_000_004_000_000);
+    }
+
     #[test]
     fn test_xrange_1() {
         let sensor = (5, 5, 2);
@@ -208,4 +265,9 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3
         assert_eq!(get_xrange(&sensor, 2), None);
         assert_eq!(get_xrange(&sensor, -1), None);
     }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(15, INPUT);
+    }
 }
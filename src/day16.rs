@@ -0,0 +1,217 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
+use std::collections::HashMap;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 16,
+    title: "Proboscidea Volcanium",
+    tags: &[Tag::Parsing, Tag::Pathfinding, Tag::Recursion],
+    complexity: "O(V³) shortest paths, a search over the orders of opening the k valves with flow, O(2^k·k) to split them between two",
+};
+
+struct Valve<'a> {
+    name: &'a str,
+    flow_rate: u32,
+    tunnels: Vec<&'a str>,
+}
+
+fn parse_valve(line: &str) -> Valve<'_> {
+    // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+    // The last part is singular for a single tunnel: "tunnel leads to valve GG"
+    let (valve, tunnels) = line.split_once("; ").unwrap();
+    let [flow_rate] = parsing::extract_array(valve).unwrap();
+    Valve {
+        name: valve.split_whitespace().nth(1).unwrap(),
+        flow_rate,
+        tunnels: tunnels
+            .split_whitespace()
+            .skip(4)
+            .map(|name| name.trim_end_matches(','))
+            .collect(),
+    }
+}
+
+/* The valves with flow, their distances to each other, and the distances from AA to them. The
+ * valves without flow only take time to walk through, so the search only visits those with flow */
+struct Network {
+    flow_rates: Vec<u32>,
+    distances: Vec<Vec<u32>>,
+    from_start: Vec<u32>,
+}
+
+fn parse_network(content: &str) -> Network {
+    let valves: Vec<Valve> = content.lines().map(parse_valve).collect();
+    let index: HashMap<&str, usize> = valves
+        .iter()
+        .enumerate()
+        .map(|(i, valve)| (valve.name, i))
+        .collect();
+
+    // Floyd-Warshall
+    let n = valves.len();
+    let unreachable = u32::MAX / 2;
+    let mut distances = vec![vec![unreachable; n]; n];
+    for (i, valve) in valves.iter().enumerate() {
+        distances[i][i] = 0;
+        for tunnel in &valve.tunnels {
+            distances[i][index[tunnel]] = 1;
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                let through_k = distances[i][k] + distances[k][j];
+                if through_k < distances[i][j] {
+                    distances[i][j] = through_k;
+                }
+            }
+        }
+    }
+
+    let with_flow: Vec<usize> = (0..n).filter(|&i| valves[i].flow_rate > 0).collect();
+    // Both the search and splitting the valves are exponential in their number, inputs have 15
+    assert!(with_flow.len() <= 20, "Too many valves with flow");
+    let start = index["AA"];
+    Network {
+        flow_rates: with_flow.iter().map(|&i| valves[i].flow_rate).collect(),
+        distances: with_flow
+            .iter()
+            .map(|&i| with_flow.iter().map(|&j| distances[i][j]).collect())
+            .collect(),
+        from_start: with_flow.iter().map(|&i| distances[start][i]).collect(),
+    }
+}
+
+impl Network {
+    /* Most pressure released for each set of opened valves (a bitmask), within `minutes` */
+    fn best_per_opened_set(&self, minutes: u32) -> HashMap<u32, u32> {
+        let mut best = HashMap::new();
+        best.insert(0, 0);
+        for (valve, &distance) in self.from_start.iter().enumerate() {
+            self.open(valve, distance, minutes, 0, 0, &mut best);
+        }
+        best
+    }
+
+    /* Walks to `valve` and opens it, then tries all the valves still closed from there */
+    fn open(
+        &self,
+        valve: usize,
+        distance: u32,
+        minutes_left: u32,
+        opened: u32,
+        pressure: u32,
+        best: &mut HashMap<u32, u32>,
+    ) {
+        // Opening takes a minute, and must be done before the time is up to release anything
+        if distance + 1 >= minutes_left {
+            return;
+        }
+        let minutes_left = minutes_left - distance - 1;
+        let opened = opened | 1 << valve;
+        let pressure = pressure + self.flow_rates[valve] * minutes_left;
+        let entry = best.entry(opened).or_insert(0);
+        *entry = (*entry).max(pressure);
+
+        for next in 0..self.flow_rates.len() {
+            if opened & 1 << next == 0 {
+                let distance = self.distances[valve][next];
+                self.open(next, distance, minutes_left, opened, pressure, best);
+            }
+        }
+    }
+}
+
+#[aoc_generator(day16)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day16, part1)]
+pub(crate) fn part1(content: &str) -> u32 {
+    let network = parse_network(content);
+    *network.best_per_opened_set(30).values().max().unwrap()
+}
+
+#[aoc(day16, part2)]
+pub(crate) fn part2(content: &str) -> u32 {
+    // We and the elephant open disjoint sets of valves, each on their own
+    let network = parse_network(content);
+    let valves = network.flow_rates.len();
+    let best = network.best_per_opened_set(26);
+
+    // Best for any subset of each set of valves, so that the elephant can take the best of the
+    // valves we don't open. Built one valve at a time: a set is at least as good as without that valve
+    let mut best_within = vec![0; 1 << valves];
+    for (&opened, &pressure) in &best {
+        best_within[opened as usize] = pressure;
+    }
+    for valve in 0..valves {
+        for set in 0..best_within.len() {
+            if set & 1 << valve != 0 {
+                best_within[set] = best_within[set].max(best_within[set ^ 1 << valve]);
+            }
+        }
+    }
+
+    let all = (1 << valves) - 1;
+    best.iter()
+        .map(|(&opened, &pressure)| pressure + best_within[(all ^ opened) as usize])
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 1651);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 1707);
+    }
+
+    #[test]
+    fn test_parse_valve() {
+        let valve = parse_valve("Valve HH has flow rate=22; tunnel leads to valve GG");
+        assert_eq!(
+            (valve.name, valve.flow_rate, valve.tunnels),
+            ("HH", 22, vec!["GG"])
+        );
+    }
+
+    #[test]
+    fn test_network() {
+        let network = parse_network(INPUT);
+        // BB, CC, DD, EE, HH and JJ
+        assert_eq!(network.flow_rates, vec![13, 2, 20, 3, 22, 21]);
+        assert_eq!(network.from_start, vec![1, 2, 1, 2, 5, 2]);
+        // HH to JJ: GG, FF, EE, DD, AA, II, JJ
+        assert_eq!(network.distances[4][5], 7);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(16, INPUT);
+    }
+}
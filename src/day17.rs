@@ -0,0 +1,219 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
+use std::collections::HashMap;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 17,
+    title: "Pyroclastic Flow",
+    tags: &[Tag::Simulation, Tag::Grid],
+    complexity:
+        "O(rocks × fall) until the chamber repeats itself, the remaining cycles are skipped",
+};
+
+/* Rows of the chamber and the rocks are bitmasks like in day 6, bit 6 being the left wall side.
+ * Rocks are listed bottom row first, as they appear: two units away from the left wall */
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+const LEFT_WALL: u8 = 0b1000000;
+const RIGHT_WALL: u8 = 0b0000001;
+const SURFACE_DEPTH: usize = 64;
+
+struct Chamber {
+    rows: Vec<u8>,
+    jets: Vec<u8>,
+    // Next jet and rock
+    jet: usize,
+    rock: usize,
+}
+
+impl Chamber {
+    fn new(content: &str) -> Chamber {
+        Chamber {
+            rows: Vec::new(),
+            jets: content.trim().bytes().collect(),
+            jet: 0,
+            rock: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /* Does the rock overlap the rocks at rest, with its bottom row at `y`? */
+    fn collides(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(i, row)| self.rows.get(y + i).is_some_and(|rest| rest & row != 0))
+    }
+
+    fn push(&self, rock: &[u8], jet: u8) -> Vec<u8> {
+        match jet {
+            b'<' if rock.iter().all(|row| row & LEFT_WALL == 0) => {
+                rock.iter().map(|row| row << 1).collect()
+            }
+            b'>' if rock.iter().all(|row| row & RIGHT_WALL == 0) => {
+                rock.iter().map(|row| row >> 1).collect()
+            }
+            _ => rock.to_vec(),
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rock].to_vec();
+        self.rock = (self.rock + 1) % ROCKS.len();
+        let mut y = self.height() + 3;
+        loop {
+            let pushed = self.push(&rock, self.jets[self.jet]);
+            self.jet = (self.jet + 1) % self.jets.len();
+            if !self.collides(&pushed, y) {
+                rock = pushed;
+            }
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (i, row) in rock.into_iter().enumerate() {
+            if y + i == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y + i] |= row;
+        }
+    }
+
+    /* How far down each column is the first rock, which is all the next rocks can touch
+     * (save for ones sliding under an overhang, which doesn't happen in practice). Deeper than
+     * SURFACE_DEPTH counts as SURFACE_DEPTH, so that a column that stays empty isn't scanned whole */
+    fn surface(&self) -> [usize; 7] {
+        let mut depths = [SURFACE_DEPTH; 7];
+        for (column, depth) in depths.iter_mut().enumerate() {
+            let bit = LEFT_WALL >> column;
+            let mut top_rows = self.rows.iter().rev().take(SURFACE_DEPTH);
+            if let Some(found) = top_rows.position(|row| row & bit != 0) {
+                *depth = found;
+            }
+        }
+        depths
+    }
+}
+
+fn tower_height(content: &str, rocks: u64) -> u64 {
+    let mut chamber = Chamber::new(content);
+    // Once the same rock falls with the same jet on the same surface, everything repeats. The
+    // rocks dropped and height at each state, to see how much the tower grows in a cycle
+    let mut seen: HashMap<(usize, usize, [usize; 7]), (u64, u64)> = HashMap::new();
+    let mut dropped = 0;
+    let mut skipped_height = 0;
+    while dropped < rocks {
+        chamber.drop_rock();
+        dropped += 1;
+        if skipped_height > 0 {
+            continue;
+        }
+        let state = (chamber.jet, chamber.rock, chamber.surface());
+        let height = chamber.height() as u64;
+        if let Some((dropped_before, height_before)) = seen.insert(state, (dropped, height)) {
+            let cycle = dropped - dropped_before;
+            let cycles = (rocks - dropped) / cycle;
+            dropped += cycles * cycle;
+            skipped_height = cycles * (height - height_before);
+        }
+    }
+    chamber.height() as u64 + skipped_height
+}
+
+#[aoc_generator(day17)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day17, part1)]
+pub(crate) fn part1(content: &str) -> u64 {
+    tower_height(content, 2022)
+}
+
+#[aoc(day17, part2)]
+pub(crate) fn part2(content: &str) -> u64 {
+    tower_height(content, 1_000_000_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>
+";
+
+    fn draw(chamber: &Chamber) -> String {
+        let mut drawing = String::new();
+        for row in chamber.rows.iter().rev() {
+            drawing.push('|');
+            for column in 0..7 {
+                drawing.push(if row & LEFT_WALL >> column != 0 {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            drawing += "|\n";
+        }
+        drawing + "+-------+\n"
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 3068);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 1514285714288);
+    }
+
+    #[test]
+    fn test_first_rocks() {
+        let mut chamber = Chamber::new(INPUT);
+        for _ in 0..3 {
+            chamber.drop_rock();
+        }
+        assert_eq!(
+            draw(&chamber),
+            "|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+
+"
+        );
+    }
+
+    #[test]
+    fn test_cycle_skipping() {
+        // Same height as dropping every rock
+        for rocks in [2023, 5000, 7777] {
+            let mut chamber = Chamber::new(INPUT);
+            for _ in 0..rocks {
+                chamber.drop_rock();
+            }
+            assert_eq!(tower_height(INPUT, rocks), chamber.height() as u64);
+        }
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(17, INPUT);
+    }
+}
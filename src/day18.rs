@@ -0,0 +1,173 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
+use std::collections::{HashSet, VecDeque};
+use std::ops::Add;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 18,
+    title: "Boiling Boulders",
+    tags: &[Tag::Grid],
+    complexity: "O(n) faces for part 1, O(volume of the bounding box) flood fill for part 2",
+};
+
+/* Like day 9's Coord, in three dimensions */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Point3 {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+const DIRECTIONS: [Point3; 6] = [
+    Point3 { x: 1, y: 0, z: 0 },
+    Point3 { x: -1, y: 0, z: 0 },
+    Point3 { x: 0, y: 1, z: 0 },
+    Point3 { x: 0, y: -1, z: 0 },
+    Point3 { x: 0, y: 0, z: 1 },
+    Point3 { x: 0, y: 0, z: -1 },
+];
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Point3 {
+    /* The cubes sharing a face with this one */
+    fn neighbors(self) -> impl Iterator<Item = Point3> {
+        DIRECTIONS
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+
+    fn within(self, min: Point3, max: Point3) -> bool {
+        (min.x..=max.x).contains(&self.x)
+            && (min.y..=max.y).contains(&self.y)
+            && (min.z..=max.z).contains(&self.z)
+    }
+}
+
+fn parse_cubes(content: &str) -> HashSet<Point3> {
+    content
+        .lines()
+        .map(|line| {
+            let [x, y, z] = parsing::extract_array(line).unwrap();
+            Point3 { x, y, z }
+        })
+        .collect()
+}
+
+#[aoc_generator(day18)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day18, part1)]
+pub(crate) fn part1(content: &str) -> usize {
+    let cubes = parse_cubes(content);
+    cubes
+        .iter()
+        .flat_map(|cube| cube.neighbors())
+        .filter(|neighbor| !cubes.contains(neighbor))
+        .count()
+}
+
+#[aoc(day18, part2)]
+pub(crate) fn part2(content: &str) -> usize {
+    let cubes = parse_cubes(content);
+    // One more than the droplet in every direction, so that the outside is connected all around it
+    let min = Point3 {
+        x: cubes.iter().map(|c| c.x).min().unwrap() - 1,
+        y: cubes.iter().map(|c| c.y).min().unwrap() - 1,
+        z: cubes.iter().map(|c| c.z).min().unwrap() - 1,
+    };
+    let max = Point3 {
+        x: cubes.iter().map(|c| c.x).max().unwrap() + 1,
+        y: cubes.iter().map(|c| c.y).max().unwrap() + 1,
+        z: cubes.iter().map(|c| c.z).max().unwrap() + 1,
+    };
+
+    // Flood fill the outside, counting the faces of the droplet the steam runs into
+    let mut outside = HashSet::from([min]);
+    let mut queue = VecDeque::from([min]);
+    let mut exterior_faces = 0;
+    while let Some(air) = queue.pop_front() {
+        for neighbor in air.neighbors() {
+            if cubes.contains(&neighbor) {
+                exterior_faces += 1;
+            } else if neighbor.within(min, max) && outside.insert(neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    exterior_faces
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1("1,1,1\n2,1,1\n"), 10);
+        assert_eq!(part1(INPUT), 64);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 58);
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        // 3x3x3 shell with an air pocket in the middle: all 54 outer faces, none of the 6 inner ones
+        let mut shell = String::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                for z in 0..3 {
+                    if (x, y, z) != (1, 1, 1) {
+                        shell += &format!("{},{},{}\n", x, y, z);
+                    }
+                }
+            }
+        }
+        assert_eq!(part1(&shell), 60);
+        assert_eq!(part2(&shell), 54);
+    }
+
+    #[test]
+    fn test_negative_coordinates() {
+        assert_eq!(part2("-1,0,0\n0,0,0\n"), 10);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(18, INPUT);
+    }
+}
@@ -0,0 +1,200 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 19,
+    title: "Not Enough Minerals",
+    tags: &[Tag::Parsing, Tag::Recursion],
+    complexity:
+        "Exponential in the minutes, kept in check by pruning; blueprints are searched in parallel",
+};
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+struct Blueprint {
+    id: u32,
+    // What each kind of robot costs, indexed by ORE, CLAY, OBSIDIAN and GEODE
+    costs: [[u32; 4]; 4],
+    // Spending is limited to one robot a minute, more robots than any cost doesn't help
+    max_needed: [u32; 4],
+}
+
+fn parse_blueprint(line: &str) -> Blueprint {
+    let [id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+        parsing::extract_array(line).unwrap();
+    let costs = [
+        [ore_ore, 0, 0, 0],
+        [clay_ore, 0, 0, 0],
+        [obsidian_ore, obsidian_clay, 0, 0],
+        [geode_ore, 0, geode_obsidian, 0],
+    ];
+    let mut max_needed = [u32::MAX; 4];
+    for resource in [ORE, CLAY, OBSIDIAN] {
+        max_needed[resource] = costs.iter().map(|cost| cost[resource]).max().unwrap();
+    }
+    Blueprint {
+        id,
+        costs,
+        max_needed,
+    }
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    minutes_left: u32,
+    robots: [u32; 4],
+    resources: [u32; 4],
+}
+
+impl Blueprint {
+    fn max_geodes(&self, minutes: u32) -> u32 {
+        let start = State {
+            minutes_left: minutes,
+            robots: [1, 0, 0, 0],
+            resources: [0; 4],
+        };
+        let mut best = 0;
+        self.search(start, &mut best);
+        best
+    }
+
+    /* Minutes to wait until the robot can be built, if the current robots ever collect enough */
+    fn wait_for(&self, state: &State, robot: usize) -> Option<u32> {
+        let mut wait = 0;
+        for (resource, &cost) in self.costs[robot].iter().enumerate() {
+            if cost <= state.resources[resource] {
+                continue;
+            }
+            if state.robots[resource] == 0 {
+                return None;
+            }
+            let missing = cost - state.resources[resource];
+            wait = wait.max(missing.div_ceil(state.robots[resource]));
+        }
+        Some(wait)
+    }
+
+    /* Depth-first over which robot to build next rather than what to do each minute, skipping
+     * the minutes spent waiting for the resources */
+    fn search(&self, state: State, best: &mut u32) {
+        let t = state.minutes_left;
+        let geodes = state.resources[GEODE] + state.robots[GEODE] * t;
+        *best = (*best).max(geodes);
+        // Even building a geode robot every minute left wouldn't beat the best
+        if geodes + t * t.saturating_sub(1) / 2 <= *best {
+            return;
+        }
+
+        for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+            // Enough robots, or enough stock, to spend the most it takes every minute left
+            let needed = self.max_needed[robot].saturating_mul(t);
+            if state.robots[robot] >= self.max_needed[robot]
+                || state.resources[robot] + state.robots[robot] * t >= needed
+            {
+                continue;
+            }
+            let wait = match self.wait_for(&state, robot) {
+                // A robot built in the last minute doesn't collect anything
+                Some(wait) if wait + 1 < t => wait,
+                _ => continue,
+            };
+            let mut next = state;
+            next.minutes_left = t - wait - 1;
+            for resource in 0..4 {
+                next.resources[resource] += state.robots[resource] * (wait + 1);
+                next.resources[resource] -= self.costs[robot][resource];
+            }
+            next.robots[robot] += 1;
+            self.search(next, best);
+        }
+    }
+}
+
+/* Each blueprint on its own thread, they don't depend on each other. Sequential on wasm32, which
+ * has no threads */
+fn max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    if cfg!(target_arch = "wasm32") {
+        return blueprints.iter().map(|b| b.max_geodes(minutes)).collect();
+    }
+    std::thread::scope(|scope| {
+        let searches: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| scope.spawn(move || blueprint.max_geodes(minutes)))
+            .collect();
+        searches
+            .into_iter()
+            .map(|search| search.join().unwrap())
+            .collect()
+    })
+}
+
+#[aoc_generator(day19)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day19, part1)]
+pub(crate) fn part1(content: &str) -> u32 {
+    let blueprints: Vec<Blueprint> = content.lines().map(parse_blueprint).collect();
+    max_geodes(&blueprints, 24)
+        .iter()
+        .zip(&blueprints)
+        .map(|(geodes, blueprint)| geodes * blueprint.id)
+        .sum()
+}
+
+#[aoc(day19, part2)]
+pub(crate) fn part2(content: &str) -> u32 {
+    let blueprints: Vec<Blueprint> = content.lines().take(3).map(parse_blueprint).collect();
+    max_geodes(&blueprints, 32).iter().product()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 33);
+    }
+
+    #[test]
+    fn test_part_2() {
+        // Only two blueprints in the sample
+        assert_eq!(part2(INPUT), 56 * 62);
+    }
+
+    #[test]
+    fn test_max_geodes() {
+        let blueprints: Vec<Blueprint> = INPUT.lines().map(parse_blueprint).collect();
+        assert_eq!(max_geodes(&blueprints, 24), vec![9, 12]);
+        assert_eq!(blueprints[1].max_geodes(32), 62);
+    }
+
+    #[test]
+    fn test_parse_blueprint() {
+        let blueprint = parse_blueprint(INPUT.lines().next().unwrap());
+        assert_eq!(blueprint.id, 1);
+        assert_eq!(
+            blueprint.costs,
+            [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]]
+        );
+        assert_eq!(blueprint.max_needed, [4, 14, 7, u32::MAX]);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(19, INPUT);
+    }
+}
@@ -0,0 +1,247 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::generators::Rng;
+use crate::metadata::{Metadata, Tag};
+use crate::{overflow, parsing, utils};
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 20,
+    title: "Grove Positioning System",
+    tags: &[Tag::Simulation, Tag::Math],
+    complexity: "O(n√n) per round with buckets of √n numbers, O(n²) for the Naive variant",
+};
+
+const DECRYPTION_KEY: i64 = 811589153;
+
+/* The circular list as buckets of about √n numbers (by their index in the input, as numbers
+ * repeat), so that finding, removing and inserting a number only walks the bucket lengths and one
+ * bucket instead of shifting the whole list */
+struct Buckets {
+    buckets: Vec<Vec<usize>>,
+    // Which bucket each number is in
+    bucket_of: Vec<usize>,
+    size: usize,
+}
+
+impl Buckets {
+    fn new(len: usize) -> Buckets {
+        let size = (len as f64).sqrt().ceil().max(1.0) as usize;
+        let mut buckets = Buckets {
+            buckets: vec![(0..len).collect()],
+            bucket_of: vec![0; len],
+            size,
+        };
+        buckets.rebalance();
+        buckets
+    }
+
+    fn order(&self) -> Vec<usize> {
+        self.buckets.concat()
+    }
+
+    /* Splits the list in buckets of `size` again, once inserting made one bucket too large */
+    fn rebalance(&mut self) {
+        let order = self.order();
+        self.buckets = order
+            .chunks(self.size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        for (bucket, numbers) in self.buckets.iter().enumerate() {
+            for &number in numbers {
+                self.bucket_of[number] = bucket;
+            }
+        }
+    }
+
+    /* Takes out the number, returning where it was */
+    fn remove(&mut self, number: usize) -> usize {
+        let bucket = self.bucket_of[number];
+        let index = self.buckets[bucket]
+            .iter()
+            .position(|&n| n == number)
+            .unwrap();
+        self.buckets[bucket].remove(index);
+        self.buckets[..bucket].iter().map(Vec::len).sum::<usize>() + index
+    }
+
+    fn insert(&mut self, position: usize, number: usize) {
+        let mut position = position;
+        let mut bucket = 0;
+        // Past the end of the last bucket is still in the last bucket
+        while bucket < self.buckets.len() - 1 && position > self.buckets[bucket].len() {
+            position -= self.buckets[bucket].len();
+            bucket += 1;
+        }
+        self.buckets[bucket].insert(position, number);
+        self.bucket_of[number] = bucket;
+        if self.buckets[bucket].len() > 2 * self.size {
+            self.rebalance();
+        }
+    }
+}
+
+fn parse_numbers(content: &str) -> Vec<i64> {
+    content
+        .lines()
+        .map(|line| parsing::number(line).unwrap())
+        .collect()
+}
+
+/* Moving a number around the others: n - 1 steps get it back where it was */
+fn new_position(position: usize, value: i64, len: usize) -> usize {
+    (position as i64 + value).rem_euclid(len as i64 - 1) as usize
+}
+
+/* The numbers after mixing them `rounds` times */
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    if numbers.len() < 2 {
+        return numbers.to_vec();
+    }
+    let mut buckets = Buckets::new(numbers.len());
+    for _ in 0..rounds {
+        for (number, &value) in numbers.iter().enumerate() {
+            let position = buckets.remove(number);
+            buckets.insert(new_position(position, value, numbers.len()), number);
+        }
+    }
+    buckets.order().into_iter().map(|i| numbers[i]).collect()
+}
+
+/* Moving the numbers in a Vec, for checking the buckets */
+fn mix_naive(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    if numbers.len() < 2 {
+        return numbers.to_vec();
+    }
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    for _ in 0..rounds {
+        for (number, &value) in numbers.iter().enumerate() {
+            let position = order.iter().position(|&n| n == number).unwrap();
+            order.remove(position);
+            order.insert(new_position(position, value, numbers.len()), number);
+        }
+    }
+    order.into_iter().map(|i| numbers[i]).collect()
+}
+
+fn grove_coordinates(mixed: &[i64]) -> i64 {
+    let zero = mixed.iter().position(|&value| value == 0).unwrap();
+    let coordinates = [1000, 2000, 3000].map(|offset| mixed[(zero + offset) % mixed.len()]);
+    overflow::sum(coordinates, "grove coordinates")
+}
+
+fn decrypt(numbers: Vec<i64>) -> Vec<i64> {
+    numbers
+        .into_iter()
+        .map(|value| overflow::mul(value, DECRYPTION_KEY, "decrypted number"))
+        .collect()
+}
+
+#[aoc_generator(day20)]
+#[aoc_generator(day20, part1, Naive)]
+#[aoc_generator(day20, part2, Naive)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day20, part1)]
+pub(crate) fn part1(content: &str) -> i64 {
+    grove_coordinates(&mix(&parse_numbers(content), 1))
+}
+
+#[aoc(day20, part2)]
+pub(crate) fn part2(content: &str) -> i64 {
+    grove_coordinates(&mix(&decrypt(parse_numbers(content)), 10))
+}
+
+#[aoc(day20, part1, Naive)]
+pub(crate) fn part1_naive(content: &str) -> i64 {
+    grove_coordinates(&mix_naive(&parse_numbers(content), 1))
+}
+
+#[aoc(day20, part2, Naive)]
+pub(crate) fn part2_naive(content: &str) -> i64 {
+    grove_coordinates(&mix_naive(&decrypt(parse_numbers(content)), 10))
+}
+
+pub(crate) fn generate(rng: &mut Rng) -> String {
+    // Lots of duplicates, and a single 0 like the real input
+    let mut numbers: Vec<i64> = (0..rng.range(2, 300))
+        .map(|_| rng.range(-50, 50))
+        .filter(|&value| value != 0)
+        .collect();
+    let zero = rng.range(0, numbers.len() as i64) as usize;
+    numbers.insert(zero, 0);
+    numbers
+        .iter()
+        .map(|value| value.to_string() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "1
+2
+-3
+3
+-2
+0
+4
+";
+
+    /* The mixed numbers starting from 0, as the puzzle doesn't care where the list starts */
+    fn from_zero(mixed: Vec<i64>) -> Vec<i64> {
+        let zero = mixed.iter().position(|&value| value == 0).unwrap();
+        [&mixed[zero..], &mixed[..zero]].concat()
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 3);
+        assert_eq!(part1_naive(INPUT), 3);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 1623178306);
+        assert_eq!(part2_naive(INPUT), 1623178306);
+    }
+
+    #[test]
+    fn test_mix() {
+        let numbers = parse_numbers(INPUT);
+        assert_eq!(from_zero(mix(&numbers, 1)), vec![0, 3, -2, 1, 2, -3, 4]);
+        assert_eq!(
+            from_zero(mix(&decrypt(numbers), 10)),
+            vec![
+                0,
+                -2434767459,
+                1623178306,
+                3246356612,
+                -1623178306,
+                2434767459,
+                811589153
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mix_like_naive() {
+        let mut rng = Rng::new(20);
+        for _ in 0..50 {
+            let numbers = parse_numbers(&generate(&mut rng));
+            assert_eq!(
+                from_zero(mix(&numbers, 3)),
+                from_zero(mix_naive(&numbers, 3))
+            );
+        }
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(20, INPUT);
+    }
+}
@@ -0,0 +1,235 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{overflow, parsing, utils};
+use std::collections::HashMap;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 21,
+    title: "Monkey Math",
+    tags: &[Tag::Parsing, Tag::Recursion, Tag::Math],
+    complexity: "O(n) to evaluate the expressions, O(n) to invert the ones on the way to humn",
+};
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+/* Like the operations of day 11's monkeys, between the numbers yelled by two other monkeys */
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, PartialEq)]
+enum Job<'a> {
+    Number(i64),
+    Operation(&'a str, Operator, &'a str),
+}
+
+fn parse_monkey(line: &str) -> (&str, Job<'_>) {
+    // root: pppw + sjmn
+    // dbpl: 5
+    let (name, job) = line.split_once(": ").unwrap();
+    let job = match job.split_whitespace().collect::<Vec<&str>>()[..] {
+        [left, operator, right] => {
+            let operator = match operator {
+                "+" => Operator::Add,
+                "-" => Operator::Subtract,
+                "*" => Operator::Multiply,
+                "/" => Operator::Divide,
+                _ => panic!("Unknown operator {}", operator),
+            };
+            Job::Operation(left, operator, right)
+        }
+        _ => Job::Number(parsing::number(job).unwrap()),
+    };
+    (name, job)
+}
+
+fn apply(left: i64, operator: Operator, right: i64) -> i64 {
+    match operator {
+        Operator::Add => overflow::add(left, right, "monkey number"),
+        Operator::Subtract => overflow::sub(left, right, "monkey number"),
+        Operator::Multiply => overflow::mul(left, right, "monkey number"),
+        Operator::Divide => {
+            assert!(right != 0 && left % right == 0, "{} / {}", left, right);
+            left / right
+        }
+    }
+}
+
+struct Monkeys<'a> {
+    jobs: HashMap<&'a str, Job<'a>>,
+    // Monkeys can be waited for by more than one other monkey, compute their numbers once
+    numbers: HashMap<&'a str, i64>,
+    // Same for whether their number depends on humn
+    with_human: HashMap<&'a str, bool>,
+}
+
+impl<'a> Monkeys<'a> {
+    fn parse(content: &'a str) -> Monkeys<'a> {
+        Monkeys {
+            jobs: content.lines().map(parse_monkey).collect(),
+            numbers: HashMap::new(),
+            with_human: HashMap::new(),
+        }
+    }
+
+    fn evaluate(&mut self, name: &'a str) -> i64 {
+        if let Some(&number) = self.numbers.get(name) {
+            return number;
+        }
+        let number = match self.jobs[name] {
+            Job::Number(number) => number,
+            Job::Operation(left, operator, right) => {
+                let left = self.evaluate(left);
+                let right = self.evaluate(right);
+                apply(left, operator, right)
+            }
+        };
+        self.numbers.insert(name, number);
+        number
+    }
+
+    fn depends_on_human(&mut self, name: &'a str) -> bool {
+        if let Some(&depends) = self.with_human.get(name) {
+            return depends;
+        }
+        let depends = name == HUMAN
+            || match self.jobs[name] {
+                Job::Number(_) => false,
+                Job::Operation(left, _, right) => {
+                    self.depends_on_human(left) | self.depends_on_human(right)
+                }
+            };
+        self.with_human.insert(name, depends);
+        depends
+    }
+
+    /* The number humn has to yell for the monkey `name` to yell `target`. Only one side of each
+     * operation on the way depends on humn, so each step undoes an operation with a known number */
+    fn solve(&mut self, name: &'a str, target: i64) -> i64 {
+        if name == HUMAN {
+            return target;
+        }
+        let (left, operator, right) = match self.jobs[name] {
+            Job::Operation(left, operator, right) => (left, operator, right),
+            Job::Number(_) => panic!("{} doesn't depend on {}", name, HUMAN),
+        };
+        if self.depends_on_human(left) {
+            // left ? right = target
+            let right = self.evaluate(right);
+            let left_target = match operator {
+                Operator::Add => apply(target, Operator::Subtract, right),
+                Operator::Subtract => apply(target, Operator::Add, right),
+                Operator::Multiply => apply(target, Operator::Divide, right),
+                Operator::Divide => apply(target, Operator::Multiply, right),
+            };
+            self.solve(left, left_target)
+        } else {
+            // left ? right = target, the order matters for subtracting and dividing
+            let left = self.evaluate(left);
+            let right_target = match operator {
+                Operator::Add => apply(target, Operator::Subtract, left),
+                Operator::Subtract => apply(left, Operator::Subtract, target),
+                Operator::Multiply => apply(target, Operator::Divide, left),
+                Operator::Divide => apply(left, Operator::Divide, target),
+            };
+            self.solve(right, right_target)
+        }
+    }
+}
+
+#[aoc_generator(day21)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day21, part1)]
+pub(crate) fn part1(content: &str) -> i64 {
+    Monkeys::parse(content).evaluate(ROOT)
+}
+
+#[aoc(day21, part2)]
+pub(crate) fn part2(content: &str) -> i64 {
+    let mut monkeys = Monkeys::parse(content);
+    // root checks that both sides are equal: whatever the side without humn yells
+    let (left, right) = match monkeys.jobs[ROOT] {
+        Job::Operation(left, _, right) => (left, right),
+        Job::Number(_) => panic!("{} has no equation", ROOT),
+    };
+    if monkeys.depends_on_human(left) {
+        let target = monkeys.evaluate(right);
+        monkeys.solve(left, target)
+    } else {
+        let target = monkeys.evaluate(left);
+        monkeys.solve(right, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 152);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 301);
+    }
+
+    #[test]
+    fn test_parse_monkey() {
+        assert_eq!(parse_monkey("dbpl: 5"), ("dbpl", Job::Number(5)));
+        assert_eq!(
+            parse_monkey("ptdq: humn - dvpt"),
+            ("ptdq", Job::Operation("humn", Operator::Subtract, "dvpt"))
+        );
+    }
+
+    #[test]
+    fn test_humn_on_the_right() {
+        // root: 10 == 100 / (30 - humn) with humn = 20
+        let input = "root: aaaa + bbbb
+aaaa: 10
+bbbb: cccc / dddd
+cccc: 100
+dddd: eeee - humn
+eeee: 30
+humn: 0
+";
+        assert_eq!(part2(input), 20);
+        // And root on the other side
+        assert_eq!(part2(&input.replace("aaaa + bbbb", "bbbb + aaaa")), 20);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(21, INPUT);
+    }
+}
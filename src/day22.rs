@@ -0,0 +1,423 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::{parsing, utils};
+use std::collections::{HashMap, VecDeque};
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 22,
+    title: "Monkey Map",
+    tags: &[Tag::Grid, Tag::Simulation, Tag::Parsing],
+    complexity: "O(length of the path), folding the cube is O(faces) once",
+};
+
+const OPEN: u8 = b'.';
+const WALL: u8 = b'#';
+const VOID: u8 = b' ';
+
+// Facing right, down, left and up, as the password counts them
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+#[derive(Debug, PartialEq)]
+enum Instruction {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position {
+    x: i32,
+    y: i32,
+    facing: usize,
+}
+
+impl Position {
+    fn step(self) -> Position {
+        let (dx, dy) = DIRECTIONS[self.facing];
+        Position {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..self
+        }
+    }
+
+    fn password(&self) -> i32 {
+        1000 * (self.y + 1) + 4 * (self.x + 1) + self.facing as i32
+    }
+}
+
+struct Board {
+    rows: Vec<Vec<u8>>,
+}
+
+impl Board {
+    fn parse(content: &str) -> Board {
+        Board {
+            rows: content.lines().map(|line| line.bytes().collect()).collect(),
+        }
+    }
+
+    /* Rows are as long as their last tile, anything off the board is VOID */
+    fn tile(&self, x: i32, y: i32) -> u8 {
+        if x < 0 || y < 0 {
+            return VOID;
+        }
+        let row = self.rows.get(y as usize);
+        *row.and_then(|row| row.get(x as usize)).unwrap_or(&VOID)
+    }
+
+    fn start(&self) -> Position {
+        let x = self.rows[0].iter().position(|&tile| tile == OPEN).unwrap();
+        Position {
+            x: x as i32,
+            y: 0,
+            facing: 0,
+        }
+    }
+
+    /* Where the path ends, `wrap` telling where stepping off the board leads */
+    fn walk<W: Fn(Position) -> Position>(&self, path: &[Instruction], wrap: W) -> Position {
+        let mut position = self.start();
+        for instruction in path {
+            match instruction {
+                Instruction::Left => position.facing = (position.facing + 3) % 4,
+                Instruction::Right => position.facing = (position.facing + 1) % 4,
+                Instruction::Forward(steps) => {
+                    for _ in 0..*steps {
+                        let mut next = position.step();
+                        if self.tile(next.x, next.y) == VOID {
+                            next = wrap(position);
+                        }
+                        if self.tile(next.x, next.y) == WALL {
+                            break;
+                        }
+                        position = next;
+                    }
+                }
+            }
+        }
+        position
+    }
+
+    /* Part 1: back to the other end of the row or column */
+    fn flat_wrap(&self, position: Position) -> Position {
+        let backwards = (position.facing + 2) % 4;
+        let mut wrapped = position;
+        loop {
+            let previous = Position {
+                facing: backwards,
+                ..wrapped
+            }
+            .step();
+            if self.tile(previous.x, previous.y) == VOID {
+                return wrapped;
+            }
+            wrapped = Position {
+                facing: position.facing,
+                ..previous
+            };
+        }
+    }
+}
+
+/* Vectors in the cube's space */
+type Vector = [i32; 3];
+
+fn neg(v: Vector) -> Vector {
+    v.map(|c| -c)
+}
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn scaled_sum(terms: &[(i32, Vector)]) -> Vector {
+    let mut sum = [0; 3];
+    for (factor, v) in terms {
+        for (c, value) in sum.iter_mut().zip(v) {
+            *c += factor * value;
+        }
+    }
+    sum
+}
+
+/* A face of the cube: where it is on the board, and where its outward normal, its right (x) and
+ * its down (y) directions on the board point once folded */
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    x: i32,
+    y: i32,
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+impl Face {
+    /* The direction facing `facing` on this face points to, once folded */
+    fn axis(&self, facing: usize) -> Vector {
+        [self.right, self.down, neg(self.right), neg(self.down)][facing]
+    }
+
+    /* Folding the neighbor of the face in the net, `facing` from this one, over the shared edge */
+    fn fold(&self, facing: usize, size: i32) -> Face {
+        let (dx, dy) = DIRECTIONS[facing];
+        let (normal, right, down) = match facing {
+            0 => (self.right, neg(self.normal), self.down),
+            1 => (self.down, self.right, neg(self.normal)),
+            2 => (neg(self.right), self.normal, self.down),
+            _ => (neg(self.down), self.right, self.normal),
+        };
+        Face {
+            x: self.x + dx * size,
+            y: self.y + dy * size,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+/* Part 2: the board is a net of the cube, folded by walking the faces from the first one.
+ * Nothing depends on the shape of the net, the sample and the real input have different ones */
+struct Cube {
+    size: i32,
+    faces: HashMap<(i32, i32), Face>,
+}
+
+impl Cube {
+    fn fold(board: &Board) -> Cube {
+        let tiles = board.rows.iter().flatten().filter(|&&t| t != VOID).count();
+        let size = ((tiles / 6) as f64).sqrt() as i32;
+        assert_eq!((size * size * 6) as usize, tiles, "Not a net of a cube");
+
+        let start = board.start();
+        let first = Face {
+            x: start.x - start.x % size,
+            y: 0,
+            normal: [0, 0, -1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        };
+        let mut faces = HashMap::from([((first.x, first.y), first)]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            for facing in 0..4 {
+                let neighbor = face.fold(facing, size);
+                let key = (neighbor.x, neighbor.y);
+                if board.tile(neighbor.x, neighbor.y) != VOID && !faces.contains_key(&key) {
+                    faces.insert(key, neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        assert_eq!(faces.len(), 6, "Not a net of a cube");
+        Cube { size, faces }
+    }
+
+    fn face_of(&self, x: i32, y: i32) -> &Face {
+        &self.faces[&(x - x % self.size, y - y % self.size)]
+    }
+
+    /* Over the edge onto the face the move points to. In the cube's space, in half tiles from
+     * its center, tiles of a face are at odd coordinates: the tile past the edge is one half tile
+     * away from the face being left and one half tile closer to the face being entered */
+    fn wrap(&self, position: Position) -> Position {
+        let n = self.size;
+        let from = self.face_of(position.x, position.y);
+        let heading = from.axis(position.facing);
+        let to = self
+            .faces
+            .values()
+            .find(|face| face.normal == heading)
+            .unwrap();
+
+        let column = position.x - from.x;
+        let row = position.y - from.y;
+        let point = scaled_sum(&[
+            (n, from.normal),
+            (2 * column + 1 - n, from.right),
+            (2 * row + 1 - n, from.down),
+            (-1, from.normal),
+            (1, to.normal),
+        ]);
+        Position {
+            x: to.x + (dot(point, to.right) + n - 1) / 2,
+            y: to.y + (dot(point, to.down) + n - 1) / 2,
+            facing: (0..4).find(|&f| to.axis(f) == neg(from.normal)).unwrap(),
+        }
+    }
+}
+
+fn parse_path(line: &str) -> Vec<Instruction> {
+    let mut path = Vec::new();
+    let mut rest = line.trim();
+    while !rest.is_empty() {
+        if let Some(turns) = rest.strip_prefix('L') {
+            path.push(Instruction::Left);
+            rest = turns;
+        } else if let Some(turns) = rest.strip_prefix('R') {
+            path.push(Instruction::Right);
+            rest = turns;
+        } else {
+            let end = rest.find(['L', 'R']).unwrap_or(rest.len());
+            path.push(Instruction::Forward(parsing::number(&rest[..end]).unwrap()));
+            rest = &rest[end..];
+        }
+    }
+    path
+}
+
+fn parse(content: &str) -> (Board, Vec<Instruction>) {
+    let [board, path] = parsing::blocks(content)[..] else {
+        panic!("Expected the board and the path");
+    };
+    (Board::parse(board), parse_path(path))
+}
+
+#[aoc_generator(day22)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day22, part1)]
+pub(crate) fn part1(content: &str) -> i32 {
+    let (board, path) = parse(content);
+    board
+        .walk(&path, |position| board.flat_wrap(position))
+        .password()
+}
+
+#[aoc(day22, part2)]
+pub(crate) fn part2(content: &str) -> i32 {
+    let (board, path) = parse(content);
+    let cube = Cube::fold(&board);
+    board.walk(&path, |position| cube.wrap(position)).password()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+
+    /* A board without walls, each `#` of the net a face of `size` tiles */
+    fn empty_board(net: &str, size: usize) -> Board {
+        let mut rows = Vec::new();
+        for line in net.lines() {
+            let row: Vec<u8> = line
+                .bytes()
+                .flat_map(|face| vec![if face == b'#' { OPEN } else { VOID }; size])
+                .collect();
+            rows.extend(vec![row; size]);
+        }
+        Board { rows }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 6032);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 5031);
+    }
+
+    #[test]
+    fn test_parse_path() {
+        use Instruction::*;
+        assert_eq!(
+            parse_path("10R5L5"),
+            vec![Forward(10), Right, Forward(5), Left, Forward(5)]
+        );
+    }
+
+    #[test]
+    fn test_cube_wrap() {
+        let (board, _) = parse(INPUT);
+        let cube = Cube::fold(&board);
+        // The examples of the puzzle: A to B, C to D
+        let a = Position {
+            x: 11,
+            y: 5,
+            facing: 0,
+        };
+        assert_eq!(
+            cube.wrap(a),
+            Position {
+                x: 14,
+                y: 8,
+                facing: 1
+            }
+        );
+        let c = Position {
+            x: 10,
+            y: 11,
+            facing: 1,
+        };
+        assert_eq!(
+            cube.wrap(c),
+            Position {
+                x: 1,
+                y: 7,
+                facing: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_around_the_cube() {
+        // The sample's net, the real input's and a cross: going straight on for four faces
+        // gets back where it started, from anywhere in any direction
+        let nets = ["  #\n###\n  ##\n", " ##\n #\n##\n#\n", " #\n####\n #\n"];
+        for net in nets {
+            for size in [1, 3, 4] {
+                let board = empty_board(net, size);
+                let cube = Cube::fold(&board);
+                for (y, row) in board.rows.iter().enumerate() {
+                    for x in (0..row.len()).filter(|&x| row[x] == OPEN) {
+                        for facing in 0..4 {
+                            let start = Position {
+                                x: x as i32,
+                                y: y as i32,
+                                facing,
+                            };
+                            let mut position = start;
+                            for _ in 0..4 * size {
+                                let next = position.step();
+                                position = if board.tile(next.x, next.y) == VOID {
+                                    cube.wrap(position)
+                                } else {
+                                    next
+                                };
+                            }
+                            assert_eq!(position, start, "{} of size {}", net, size);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(22, INPUT);
+    }
+}
@@ -0,0 +1,198 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
+use std::collections::{HashMap, HashSet};
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 23,
+    title: "Unstable Diffusion",
+    tags: &[Tag::Grid, Tag::Simulation],
+    complexity: "O(elves) per round",
+};
+
+/* The three tiles an elf looks at before proposing to move north, south, west or east, the
+ * middle one being where it moves */
+const LOOKS: [[(i32, i32); 3]; 4] = [
+    [(0, -1), (-1, -1), (1, -1)],
+    [(0, 1), (-1, 1), (1, 1)],
+    [(-1, 0), (-1, -1), (-1, 1)],
+    [(1, 0), (1, -1), (1, 1)],
+];
+
+const AROUND: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/* Only where the elves are: they spread out with no bound on the grove */
+struct Grove {
+    elves: HashSet<(i32, i32)>,
+    // Which direction the elves consider first, it changes every round
+    first_look: usize,
+}
+
+impl Grove {
+    fn parse(content: &str) -> Grove {
+        let mut elves = HashSet::new();
+        for (y, line) in content.lines().enumerate() {
+            for (x, tile) in line.bytes().enumerate() {
+                if tile == b'#' {
+                    elves.insert((x as i32, y as i32));
+                }
+            }
+        }
+        Grove {
+            elves,
+            first_look: 0,
+        }
+    }
+
+    fn is_free(&self, (x, y): (i32, i32), offsets: &[(i32, i32)]) -> bool {
+        offsets
+            .iter()
+            .all(|(dx, dy)| !self.elves.contains(&(x + dx, y + dy)))
+    }
+
+    fn proposal(&self, elf: (i32, i32)) -> Option<(i32, i32)> {
+        if self.is_free(elf, &AROUND) {
+            return None;
+        }
+        (0..4)
+            .map(|i| LOOKS[(self.first_look + i) % 4])
+            .find(|look| self.is_free(elf, look))
+            .map(|[(dx, dy), _, _]| (elf.0 + dx, elf.1 + dy))
+    }
+
+    /* Returns whether any elf moved */
+    fn round(&mut self) -> bool {
+        let proposals: Vec<((i32, i32), (i32, i32))> = self
+            .elves
+            .iter()
+            .filter_map(|&elf| self.proposal(elf).map(|to| (elf, to)))
+            .collect();
+        let mut proposed: HashMap<(i32, i32), usize> = HashMap::new();
+        for (_, to) in &proposals {
+            *proposed.entry(*to).or_default() += 1;
+        }
+
+        let mut moved = false;
+        for (from, to) in proposals {
+            // Elves proposing the same tile all stay put
+            if proposed[&to] == 1 {
+                self.elves.remove(&from);
+                self.elves.insert(to);
+                moved = true;
+            }
+        }
+        self.first_look = (self.first_look + 1) % 4;
+        moved
+    }
+
+    /* The smallest rectangle around the elves, as (min x, min y, max x, max y) */
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        let xs = self.elves.iter().map(|elf| elf.0);
+        let ys = self.elves.iter().map(|elf| elf.1);
+        (
+            xs.clone().min().unwrap(),
+            ys.clone().min().unwrap(),
+            xs.max().unwrap(),
+            ys.max().unwrap(),
+        )
+    }
+
+    fn empty_ground(&self) -> usize {
+        let (min_x, min_y, max_x, max_y) = self.bounds();
+        let area = ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize;
+        area - self.elves.len()
+    }
+}
+
+#[aoc_generator(day23)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day23, part1)]
+pub(crate) fn part1(content: &str) -> usize {
+    let mut grove = Grove::parse(content);
+    for _ in 0..10 {
+        grove.round();
+    }
+    grove.empty_ground()
+}
+
+#[aoc(day23, part2)]
+pub(crate) fn part2(content: &str) -> usize {
+    let mut grove = Grove::parse(content);
+    let mut rounds = 1;
+    while grove.round() {
+        rounds += 1;
+    }
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..
+";
+
+    fn draw(grove: &Grove) -> String {
+        let (min_x, min_y, max_x, max_y) = grove.bounds();
+        let mut drawing = String::new();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                drawing.push(if grove.elves.contains(&(x, y)) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            drawing.push('\n');
+        }
+        drawing
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 110);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 20);
+    }
+
+    #[test]
+    fn test_small_example() {
+        let mut grove = Grove::parse(".....\n..##.\n..#..\n.....\n..##.\n.....\n");
+        assert!(grove.round());
+        assert_eq!(draw(&grove), "##\n..\n#.\n.#\n#.\n");
+        assert!(grove.round());
+        assert_eq!(draw(&grove), ".##.\n#...\n...#\n....\n.#..\n");
+        assert!(grove.round());
+        assert_eq!(draw(&grove), "..#..\n....#\n#....\n....#\n.....\n..#..\n");
+        assert!(!grove.round());
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(23, INPUT);
+    }
+}
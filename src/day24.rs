@@ -0,0 +1,170 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::day12::get_neighbor;
+use crate::metadata::{Metadata, Tag};
+use crate::utils;
+use std::collections::{HashSet, VecDeque};
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 24,
+    title: "Blizzard Basin",
+    tags: &[Tag::Grid, Tag::Pathfinding, Tag::Math],
+    complexity: "O(width × height × lcm(width, height)) BFS over positions and times",
+};
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+struct Valley {
+    // Inside the walls, the blizzards where they start
+    blizzards: Vec<Vec<u8>>,
+    width: i32,
+    height: i32,
+    // In the coordinates of the input, walls included
+    entrance: (i32, i32),
+    exit: (i32, i32),
+    // The blizzards are back where they started after this many minutes
+    period: usize,
+}
+
+impl Valley {
+    fn parse(content: &str) -> Valley {
+        let lines: Vec<&str> = content.lines().collect();
+        let gap = |line: &str| line.find('.').unwrap() as i32;
+        let blizzards: Vec<Vec<u8>> = lines[1..lines.len() - 1]
+            .iter()
+            .map(|line| line.as_bytes()[1..line.len() - 1].to_vec())
+            .collect();
+        let width = blizzards[0].len();
+        let height = blizzards.len();
+        Valley {
+            width: width as i32,
+            height: height as i32,
+            entrance: (gap(lines[0]), 0),
+            exit: (gap(lines[lines.len() - 1]), height as i32 + 1),
+            period: width * height / gcd(width, height),
+            blizzards,
+        }
+    }
+
+    /* Is there no blizzard on the position at that minute? Blizzards move in straight lines and
+     * wrap around, so the only ones that can be there started in the same row or column, that many
+     * tiles away */
+    fn is_clear(&self, (x, y): (i32, i32), minute: usize) -> bool {
+        if (x, y) == self.entrance || (x, y) == self.exit {
+            return true;
+        }
+        if x < 1 || x > self.width || y < 1 || y > self.height {
+            return false;
+        }
+        let (x, y) = (x - 1, y - 1);
+        let (dx, dy) = (
+            (minute % self.width as usize) as i32,
+            (minute % self.height as usize) as i32,
+        );
+        let blizzard = |x: i32, y: i32| self.blizzards[y as usize][x as usize];
+        blizzard((x - dx).rem_euclid(self.width), y) != b'>'
+            && blizzard((x + dx).rem_euclid(self.width), y) != b'<'
+            && blizzard(x, (y - dy).rem_euclid(self.height)) != b'v'
+            && blizzard(x, (y + dy).rem_euclid(self.height)) != b'^'
+    }
+
+    /* The minute the expedition gets to `to` at the earliest, setting off from `from` at `start`.
+     * Breadth-first with the time as part of the state: waiting is a move too, and the same tile
+     * at the same point of the blizzards' period was already explored */
+    fn crossing(&self, from: (i32, i32), to: (i32, i32), start: usize) -> usize {
+        let mut queue = VecDeque::from([(from, start)]);
+        let mut seen = HashSet::from([(from, start % self.period)]);
+        while let Some((position, minute)) = queue.pop_front() {
+            if position == to {
+                return minute;
+            }
+            let moves = (0..4).map(|direction| get_neighbor(&position, direction));
+            for next in moves.chain([position]) {
+                let state = (next, (minute + 1) % self.period);
+                if self.is_clear(next, minute + 1) && seen.insert(state) {
+                    queue.push_back((next, minute + 1));
+                }
+            }
+        }
+        panic!("No way from {:?} to {:?}", from, to);
+    }
+}
+
+#[aoc_generator(day24)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day24, part1)]
+pub(crate) fn part1(content: &str) -> usize {
+    let valley = Valley::parse(content);
+    valley.crossing(valley.entrance, valley.exit, 0)
+}
+
+#[aoc(day24, part2)]
+pub(crate) fn part2(content: &str) -> usize {
+    let valley = Valley::parse(content);
+    let there = valley.crossing(valley.entrance, valley.exit, 0);
+    let back = valley.crossing(valley.exit, valley.entrance, there);
+    valley.crossing(valley.entrance, valley.exit, back)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions;
+
+    const INPUT: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), 18);
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part2(INPUT), 54);
+    }
+
+    #[test]
+    fn test_blizzards() {
+        let valley = Valley::parse(INPUT);
+        assert_eq!(valley.period, 12);
+        // Where the blizzards are after a minute, and that they're back after a period
+        let draw = |minute: usize| -> Vec<String> {
+            (1..=valley.height)
+                .map(|y| {
+                    (1..=valley.width)
+                        .map(|x| {
+                            if valley.is_clear((x, y), minute) {
+                                '.'
+                            } else {
+                                '*'
+                            }
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        assert_eq!(draw(1), vec![".**.*.", "*..**.", "**.**.", "**..**"]);
+        assert_eq!(draw(valley.period), draw(0));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(24, INPUT);
+    }
+}
@@ -0,0 +1,248 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::metadata::{Metadata, Tag};
+use crate::parsing::{self, ParseError};
+use crate::utils;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+pub(crate) const METADATA: Metadata = Metadata {
+    day: 25,
+    title: "Full of Hot Air",
+    tags: &[Tag::Math, Tag::Strings],
+    complexity: "O(digits) per number, added without converting them",
+};
+
+/* Balanced base 5: digits from -2 to 2, written =, -, 0, 1 and 2. Negative numbers need no sign,
+ * their most significant digit is negative */
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Snafu {
+    // Least significant first, without zeros at the most significant end: 0 has no digits
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    fn trim(mut self) -> Snafu {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        self
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(raw: &str) -> Result<Snafu, ParseError> {
+        let invalid = || ParseError::InvalidNumber(raw.to_string());
+        if raw.is_empty() {
+            return Err(invalid());
+        }
+        let digits = raw
+            .bytes()
+            .rev()
+            .map(|digit| match digit {
+                b'=' => Ok(-2),
+                b'-' => Ok(-1),
+                b'0' => Ok(0),
+                b'1' => Ok(1),
+                b'2' => Ok(2),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<Vec<i8>, ParseError>>()?;
+        Ok(Snafu { digits }.trim())
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.digits.is_empty() {
+            return f.write_str("0");
+        }
+        for digit in self.digits.iter().rev() {
+            f.write_str(match digit {
+                -2 => "=",
+                -1 => "-",
+                0 => "0",
+                1 => "1",
+                _ => "2",
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Snafu {
+        let mut digits = Vec::new();
+        let mut rest = value;
+        while rest != 0 {
+            // A 3 or 4 is a -2 or -1 with one more in the next digit
+            let mut digit = rest.rem_euclid(5);
+            rest = rest.div_euclid(5);
+            if digit > 2 {
+                digit -= 5;
+                rest += 1;
+            }
+            digits.push(digit as i8);
+        }
+        Snafu { digits }
+    }
+}
+
+impl From<&Snafu> for i64 {
+    fn from(snafu: &Snafu) -> i64 {
+        // i64::MIN ends with a 2, multiplying by 5 before adding it would be out of range
+        snafu.digits.iter().rev().fold(0, |value, &digit| {
+            let next = value as i128 * 5 + digit as i128;
+            i64::try_from(next).unwrap_or_else(|_| panic!("Overflow in SNAFU number: {}", snafu))
+        })
+    }
+}
+
+/* Digit by digit like on paper, a sum out of -2..=2 carrying one to the next digit */
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, other: Snafu) -> Snafu {
+        let len = self.digits.len().max(other.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let a = self.digits.get(i).unwrap_or(&0);
+            let b = other.digits.get(i).unwrap_or(&0);
+            let mut digit = a + b + carry;
+            carry = 0;
+            if digit > 2 {
+                digit -= 5;
+                carry = 1;
+            } else if digit < -2 {
+                digit += 5;
+                carry = -1;
+            }
+            digits.push(digit);
+        }
+        digits.push(carry);
+        Snafu { digits }.trim()
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(numbers: I) -> Snafu {
+        numbers.fold(Snafu::default(), |total, number| total + number)
+    }
+}
+
+#[aoc_generator(day25)]
+pub(crate) fn input_generator(content: &str) -> String {
+    utils::normalize(content)
+}
+
+#[aoc(day25, part1)]
+pub(crate) fn part1(content: &str) -> String {
+    let total: Snafu = content
+        .lines()
+        .map(|line| parsing::number::<Snafu>(line).unwrap())
+        .sum();
+    total.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::generators::Rng;
+    use crate::solutions;
+
+    const INPUT: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122
+";
+
+    const DECIMALS: [(i64, &str); 15] = [
+        (1, "1"),
+        (2, "2"),
+        (3, "1="),
+        (4, "1-"),
+        (5, "10"),
+        (6, "11"),
+        (7, "12"),
+        (8, "2="),
+        (9, "2-"),
+        (10, "20"),
+        (15, "1=0"),
+        (20, "1-0"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+    ];
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part1(INPUT), "2=-1=0");
+    }
+
+    #[test]
+    fn test_decimals() {
+        for (decimal, snafu) in DECIMALS {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(i64::from(&snafu.parse::<Snafu>().unwrap()), decimal);
+        }
+        assert_eq!(Snafu::from(0).to_string(), "0");
+        assert_eq!("00".parse::<Snafu>(), Ok(Snafu::default()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for raw in ["", "3", "1=a", "-1 "] {
+            assert_eq!(
+                raw.parse::<Snafu>(),
+                Err(ParseError::InvalidNumber(raw.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = Rng::new(25);
+        let limit = i64::MAX / 2;
+        for _ in 0..1000 {
+            let value = rng.range(-limit, limit);
+            let snafu = Snafu::from(value);
+            assert_eq!(i64::from(&snafu), value);
+            assert_eq!(snafu.to_string().parse::<Snafu>(), Ok(snafu));
+        }
+        for value in [i64::MIN, i64::MAX] {
+            assert_eq!(i64::from(&Snafu::from(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        let mut rng = Rng::new(2025);
+        let limit = i64::MAX / 4;
+        for _ in 0..1000 {
+            let a = rng.range(-limit, limit);
+            let b = rng.range(-limit, limit);
+            assert_eq!(Snafu::from(a) + Snafu::from(b), Snafu::from(a + b));
+        }
+        assert_eq!(Snafu::from(7) + Snafu::from(-7), Snafu::default());
+    }
+
+    #[test]
+    fn test_input_variants() {
+        solutions::assert_input_variants(25, INPUT);
+    }
+}
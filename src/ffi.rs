@@ -0,0 +1,239 @@
+use crate::solutions;
+
+use std::os::raw::c_char;
+use std::panic;
+use std::slice;
+
+/* C ABI for calling the solutions from other languages, the matching header is include/aoc.h */
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_ERR_NULL_POINTER: i32 = 1;
+pub const AOC_ERR_UNKNOWN_YEAR: i32 = 2;
+pub const AOC_ERR_UNKNOWN_SOLUTION: i32 = 3;
+pub const AOC_ERR_INVALID_UTF8: i32 = 4;
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = 5;
+pub const AOC_ERR_PANIC: i32 = 6;
+
+const ERROR_CODES: [(&str, i32, &str); 7] = [
+    ("AOC_OK", AOC_OK, "Success, the answer is in out_buf"),
+    (
+        "AOC_ERR_NULL_POINTER",
+        AOC_ERR_NULL_POINTER,
+        "input or out_buf is NULL",
+    ),
+    (
+        "AOC_ERR_UNKNOWN_YEAR",
+        AOC_ERR_UNKNOWN_YEAR,
+        "No solutions for this year",
+    ),
+    (
+        "AOC_ERR_UNKNOWN_SOLUTION",
+        AOC_ERR_UNKNOWN_SOLUTION,
+        "No solution for this day and part",
+    ),
+    (
+        "AOC_ERR_INVALID_UTF8",
+        AOC_ERR_INVALID_UTF8,
+        "The input is not valid UTF-8",
+    ),
+    (
+        "AOC_ERR_BUFFER_TOO_SMALL",
+        AOC_ERR_BUFFER_TOO_SMALL,
+        "out_buf can't hold the answer and its terminating NUL",
+    ),
+    (
+        "AOC_ERR_PANIC",
+        AOC_ERR_PANIC,
+        "The solution panicked, most likely on malformed input",
+    ),
+];
+
+const YEAR: u32 = 2022;
+
+/// Solves a puzzle, writing the answer as NUL-terminated string into `out_buf`.
+///
+/// The length of the answer (without NUL) is written to `answer_len` when it isn't NULL,
+/// also when `out_buf` is too small so that the caller can retry with a larger buffer.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes and `out_buf` to `out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: usize,
+    answer_len: *mut usize,
+) -> i32 {
+    if input.is_null() || out_buf.is_null() {
+        return AOC_ERR_NULL_POINTER;
+    }
+    if year != YEAR {
+        return AOC_ERR_UNKNOWN_YEAR;
+    }
+    let solution = match solutions::find(day, part) {
+        Some(solution) => solution,
+        None => return AOC_ERR_UNKNOWN_SOLUTION,
+    };
+    let input = match std::str::from_utf8(slice::from_raw_parts(input, input_len)) {
+        Ok(input) => input,
+        Err(_) => return AOC_ERR_INVALID_UTF8,
+    };
+
+    // Panics must not unwind into the caller
+    let answer = match panic::catch_unwind(|| (solution.solve)(input)) {
+        Ok(answer) => answer,
+        Err(_) => return AOC_ERR_PANIC,
+    };
+
+    if !answer_len.is_null() {
+        *answer_len = answer.len();
+    }
+    if answer.len() + 1 > out_len {
+        return AOC_ERR_BUFFER_TOO_SMALL;
+    }
+    let out = slice::from_raw_parts_mut(out_buf as *mut u8, out_len);
+    out[..answer.len()].copy_from_slice(answer.as_bytes());
+    out[answer.len()] = 0;
+    AOC_OK
+}
+
+/* Contents of include/aoc.h */
+pub fn header() -> String {
+    let mut header = String::from(
+        "/* Generated from src/ffi.rs, do not edit. Regenerate with `AOC_UPDATE_HEADER=1 cargo test ffi` */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+",
+    );
+    for (name, value, description) in ERROR_CODES {
+        header.push_str(&format!(
+            "/* {} */\n#define {} {}\n",
+            description, name, value
+        ));
+    }
+    header.push_str(
+        "
+/*
+ * Solves a puzzle, writing the answer as NUL-terminated string into out_buf.
+ *
+ * The length of the answer (without NUL) is written to answer_len when it isn't NULL,
+ * also when out_buf is too small so that the caller can retry with a larger buffer.
+ * Returns AOC_OK or one of the AOC_ERR_* codes.
+ */
+int32_t aoc_solve(uint32_t year, uint32_t day, uint32_t part,
+                  const uint8_t *input, size_t input_len,
+                  char *out_buf, size_t out_len, size_t *answer_len);
+
+#endif /* AOC_H */
+",
+    );
+    header
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+    use std::ptr;
+
+    fn solve(year: u32, day: u32, part: u32, input: &str, out_len: usize) -> (i32, String, usize) {
+        let mut out = vec![0u8; out_len];
+        let mut answer_len = 0;
+        let code = unsafe {
+            aoc_solve(
+                year,
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr() as *mut c_char,
+                out.len(),
+                &mut answer_len,
+            )
+        };
+        let answer = out
+            .iter()
+            .take_while(|&&c| c != 0)
+            .map(|&c| c as char)
+            .collect();
+        (code, answer, answer_len)
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+        assert_eq!(
+            solve(2022, 1, 1, input, 64),
+            (AOC_OK, "24000".to_string(), 5)
+        );
+        assert_eq!(
+            solve(2022, 1, 2, input, 64),
+            (AOC_OK, "45000".to_string(), 5)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+        assert_eq!(solve(2021, 6, 1, input, 64).0, AOC_ERR_UNKNOWN_YEAR);
+        assert_eq!(solve(2022, 6, 3, input, 64).0, AOC_ERR_UNKNOWN_SOLUTION);
+        assert_eq!(solve(2022, 6, 2, input, 2).0, AOC_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(solve(2022, 6, 2, input, 2).2, 2);
+        assert_eq!(solve(2022, 6, 2, input, 3), (AOC_OK, "26".to_string(), 2));
+        assert_eq!(solve(2022, 1, 1, "not a number", 64).0, AOC_ERR_PANIC);
+
+        let invalid_utf8 = [0xffu8, 0xfe];
+        let mut out = [0 as c_char; 8];
+        let code = unsafe {
+            aoc_solve(
+                2022,
+                1,
+                1,
+                invalid_utf8.as_ptr(),
+                2,
+                out.as_mut_ptr(),
+                8,
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, AOC_ERR_INVALID_UTF8);
+        let code = unsafe {
+            aoc_solve(
+                2022,
+                1,
+                1,
+                ptr::null(),
+                0,
+                out.as_mut_ptr(),
+                8,
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(code, AOC_ERR_NULL_POINTER);
+    }
+
+    #[test]
+    fn test_header_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/aoc.h");
+        if env::var("AOC_UPDATE_HEADER").is_ok() {
+            fs::write(&path, header()).unwrap();
+        }
+        let committed = fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == header(),
+            "include/aoc.h is outdated, regenerate it with `AOC_UPDATE_HEADER=1 cargo test ffi`"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::*;
+
+/* Small xorshift PRNG so that generated inputs are reproducible from a seed */
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // xorshift gets stuck on 0
+        Rng(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /* Random number in the inclusive range low..=high */
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next_u64() % (high - low + 1) as u64) as i64
+    }
+}
+
+pub type Generator = fn(&mut Rng) -> String;
+
+/* Random puzzle inputs, used to cross-check solution variants */
+pub const GENERATORS: &[(u32, Generator)] = &[
+    (6, day06::generate),
+    (14, day14::generate),
+    (20, day20::generate),
+];
+
+pub fn find(day: u32) -> Option<Generator> {
+    GENERATORS
+        .iter()
+        .find(|(generator_day, _)| *generator_day == day)
+        .map(|(_, generate)| *generate)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let value = rng.range(-3, 3);
+            assert!((-3..=3).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_rng_reproducible() {
+        let mut rng1 = Rng::new(2022);
+        let mut rng2 = Rng::new(2022);
+        let generate = find(14).unwrap();
+        assert_eq!(generate(&mut rng1), generate(&mut rng2));
+    }
+}
@@ -15,5 +15,28 @@ mod day12;
 mod day13;
 mod day14;
 mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+pub mod ffi;
+pub mod generators;
+pub mod metadata;
+pub mod overflow;
+pub mod parsing;
+pub mod progress;
+#[cfg(test)]
+mod snapshot;
+pub mod solutions;
+pub mod utils;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
 
 aoc_lib! { year = 2022 }
@@ -0,0 +1,138 @@
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+};
+use std::fmt;
+
+/* What each day is about, declared next to its solutions as `METADATA` so that it's found by
+ * `tools list --tag <tag>`, i.e. all the pathfinding or interval code */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    Grid,
+    Simulation,
+    Parsing,
+    Pathfinding,
+    Intervals,
+    Strings,
+    Recursion,
+    Math,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 8] = [
+        Tag::Grid,
+        Tag::Simulation,
+        Tag::Parsing,
+        Tag::Pathfinding,
+        Tag::Intervals,
+        Tag::Strings,
+        Tag::Recursion,
+        Tag::Math,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Grid => "grid",
+            Tag::Simulation => "simulation",
+            Tag::Parsing => "parsing",
+            Tag::Pathfinding => "pathfinding",
+            Tag::Intervals => "intervals",
+            Tag::Strings => "strings",
+            Tag::Recursion => "recursion",
+            Tag::Math => "math",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Tag> {
+        Tag::ALL
+            .into_iter()
+            .find(|tag| tag.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub struct Metadata {
+    pub day: u32,
+    pub title: &'static str,
+    pub tags: &'static [Tag],
+    // Expected running time of the solutions, in terms of the input
+    pub complexity: &'static str,
+}
+
+pub const METADATA: &[Metadata] = &[
+    day01::METADATA,
+    day02::METADATA,
+    day03::METADATA,
+    day04::METADATA,
+    day05::METADATA,
+    day06::METADATA,
+    day07::METADATA,
+    day08::METADATA,
+    day09::METADATA,
+    day10::METADATA,
+    day11::METADATA,
+    day12::METADATA,
+    day13::METADATA,
+    day14::METADATA,
+    day15::METADATA,
+    day16::METADATA,
+    day17::METADATA,
+    day18::METADATA,
+    day19::METADATA,
+    day20::METADATA,
+    day21::METADATA,
+    day22::METADATA,
+    day23::METADATA,
+    day24::METADATA,
+    day25::METADATA,
+];
+
+pub fn for_day(day: u32) -> Option<&'static Metadata> {
+    METADATA.iter().find(|metadata| metadata.day == day)
+}
+
+pub fn with_tag(tag: Tag) -> impl Iterator<Item = &'static Metadata> {
+    METADATA
+        .iter()
+        .filter(move |metadata| metadata.tags.contains(&tag))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::solutions::SOLUTIONS;
+
+    #[test]
+    fn test_every_day_has_metadata() {
+        for solution in SOLUTIONS {
+            assert!(for_day(solution.day).is_some(), "day {}", solution.day);
+        }
+        for (index, metadata) in METADATA.iter().enumerate() {
+            assert_eq!(metadata.day, index as u32 + 1);
+            assert!(!metadata.title.is_empty() && !metadata.tags.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_with_tag() {
+        let days = |tag| with_tag(tag).map(|m| m.day).collect::<Vec<u32>>();
+        assert!(days(Tag::Pathfinding).contains(&12));
+        assert_eq!(days(Tag::Intervals), vec![4, 15]);
+    }
+
+    #[test]
+    fn test_parse() {
+        for tag in Tag::ALL {
+            assert_eq!(Tag::parse(tag.name()), Some(tag));
+        }
+        assert_eq!(Tag::parse("Grid"), Some(Tag::Grid));
+        assert_eq!(Tag::parse("graphs"), None);
+    }
+}
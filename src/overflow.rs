@@ -0,0 +1,109 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/* Checked arithmetic for the solvers adding up or multiplying puzzle numbers.
+ *
+ * Those solvers compute in a type wider than the puzzle needs, and every operation is checked
+ * so that an overflow panics naming what overflowed, also in release builds where it would wrap.
+ * In strict mode values must also fit the type the puzzle implies (i.e. `i32` calories), which
+ * makes large generated inputs fail loudly instead of silently relying on the wider type. */
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+pub fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+pub trait Checked: Copy + Default + Display {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! checked {
+    ($($type:ty),*) => {
+        $(impl Checked for $type {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$type>::checked_add(self, other)
+            }
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$type>::checked_sub(self, other)
+            }
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$type>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+
+checked!(i32, i64, u32, u64, u128, usize);
+
+pub fn add<T: Checked>(a: T, b: T, what: &str) -> T {
+    a.checked_add(b)
+        .unwrap_or_else(|| panic!("Overflow in {}: {} + {}", what, a, b))
+}
+
+pub fn sub<T: Checked>(a: T, b: T, what: &str) -> T {
+    a.checked_sub(b)
+        .unwrap_or_else(|| panic!("Overflow in {}: {} - {}", what, a, b))
+}
+
+pub fn mul<T: Checked>(a: T, b: T, what: &str) -> T {
+    a.checked_mul(b)
+        .unwrap_or_else(|| panic!("Overflow in {}: {} * {}", what, a, b))
+}
+
+pub fn sum<T: Checked, I: IntoIterator<Item = T>>(values: I, what: &str) -> T {
+    values
+        .into_iter()
+        .fold(T::default(), |total, value| add(total, value, what))
+}
+
+/* Passes `value` through, in strict mode only when it also fits in `N` */
+pub fn fits<N: TryFrom<T>, T: Copy + Display>(value: T, what: &str) -> T {
+    fits_when::<N, T>(value, is_strict(), what)
+}
+
+/* `fits` with the mode passed in, so that tests don't flip it for the solvers running alongside */
+fn fits_when<N: TryFrom<T>, T: Copy + Display>(value: T, strict: bool, what: &str) -> T {
+    if strict && N::try_from(value).is_err() {
+        panic!(
+            "Overflow in {}: {} doesn't fit {} (strict overflow mode)",
+            what,
+            value,
+            std::any::type_name::<N>()
+        );
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(add(1i32, 2, "test"), 3);
+        assert_eq!(mul(3u64, 4, "test"), 12);
+        assert_eq!(sum(vec![1u32, 2, 3], "test"), 6);
+        assert!(panic::catch_unwind(|| add(i32::MAX, 1, "test")).is_err());
+        assert!(panic::catch_unwind(|| sub(0u32, 1, "test")).is_err());
+        assert!(panic::catch_unwind(|| mul(u64::MAX, 2, "test")).is_err());
+        assert!(panic::catch_unwind(|| sum(vec![u32::MAX, 1], "test")).is_err());
+    }
+
+    #[test]
+    fn test_fits() {
+        let large = i32::MAX as i64 + 1;
+        assert_eq!(fits::<i32, _>(large, "test"), large);
+        assert_eq!(fits_when::<i32, _>(large, false, "test"), large);
+        assert!(panic::catch_unwind(|| fits_when::<i32, _>(large, true, "test")).is_err());
+        assert_eq!(fits_when::<i32, _>(42i64, true, "test"), 42);
+    }
+}
@@ -0,0 +1,216 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/* Shared helpers for parsing puzzle inputs, instead of every day splitting and indexing tokens itself */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    // The line doesn't look like the pattern passed to `scan`
+    Mismatch {
+        pattern: String,
+        line: String,
+    },
+    InvalidNumber(String),
+    // A different amount of numbers than expected in a line
+    Count {
+        expected: usize,
+        found: usize,
+        line: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Mismatch { pattern, line } => {
+                write!(f, "{:?} doesn't match pattern {:?}", line, pattern)
+            }
+            ParseError::InvalidNumber(raw) => write!(f, "{:?} is not a valid number", raw),
+            ParseError::Count {
+                expected,
+                found,
+                line,
+            } => write!(
+                f,
+                "Expected {} numbers but found {} in {:?}",
+                expected, found, line
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn number<T: FromStr>(raw: &str) -> Result<T, ParseError> {
+    raw.trim()
+        .parse::<T>()
+        .map_err(|_| ParseError::InvalidNumber(raw.to_string()))
+}
+
+/* All integers in a text, ignoring everything around them.
+ * A `-` is only a sign when it isn't preceded by a letter or digit, so that `2-4` is 2 and 4 */
+pub fn extract_ints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+        if negative || bytes[i].is_ascii_digit() {
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            numbers.push(number(&text[start..i])?);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(numbers)
+}
+
+/* Exactly N integers from a text, i.e. `let [x, y] = extract_array(line)?` */
+pub fn extract_array<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError> {
+    extract_ints(text)?
+        .try_into()
+        .map_err(|numbers: Vec<T>| ParseError::Count {
+            expected: N,
+            found: numbers.len(),
+            line: text.to_string(),
+        })
+}
+
+/* Matches a line against a pattern with `{}` placeholders, i.e. "move {} from {} to {}",
+ * returning what the placeholders matched. Whitespace around the line and the pattern is ignored */
+pub fn scan<'a>(pattern: &str, line: &'a str) -> Result<Vec<&'a str>, ParseError> {
+    let mismatch = || ParseError::Mismatch {
+        pattern: pattern.to_string(),
+        line: line.to_string(),
+    };
+    let mut pieces = pattern.trim().split("{}");
+    let mut rest = line
+        .trim()
+        .strip_prefix(pieces.next().unwrap())
+        .ok_or_else(mismatch)?;
+
+    let mut fields = Vec::new();
+    for literal in pieces {
+        // A placeholder takes everything up to the next literal, or the rest of the line when last
+        let end = match literal {
+            "" => rest.len(),
+            literal => rest.find(literal).ok_or_else(mismatch)?,
+        };
+        if end == 0 {
+            return Err(mismatch());
+        }
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(mismatch());
+    }
+    Ok(fields)
+}
+
+/* Blocks of lines separated by blank lines, tolerating CRLF line endings and whitespace on the
+ * separating lines. Blank lines at the start and end of the content don't make empty blocks */
+pub fn blocks(content: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            if let Some(block_start) = start.take() {
+                blocks.push(&content[block_start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + text.len();
+        }
+        offset += line.len();
+    }
+    if let Some(block_start) = start {
+        blocks.push(&content[block_start..end]);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_extract_ints() {
+        assert_eq!(
+            extract_ints::<i32>("Sensor at x=2, y=-18: closest beacon is at x=-2, y=15"),
+            Ok(vec![2, -18, -2, 15])
+        );
+        assert_eq!(extract_ints::<u32>("2-4,6-8"), Ok(vec![2, 4, 6, 8]));
+        assert_eq!(extract_ints::<u32>("no numbers - here"), Ok(vec![]));
+        assert_eq!(
+            extract_ints::<u8>("300"),
+            Err(ParseError::InvalidNumber("300".to_string()))
+        );
+        assert_eq!(
+            extract_ints::<u32>("x=-1"),
+            Err(ParseError::InvalidNumber("-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_extract_array() {
+        assert_eq!(extract_array::<i32, 2>("498,4"), Ok([498, 4]));
+        assert_eq!(
+            extract_array::<i32, 2>("1,2,3"),
+            Err(ParseError::Count {
+                expected: 2,
+                found: 3,
+                line: "1,2,3".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            scan("move {} from {} to {}", "move 13 from 2 to 1"),
+            Ok(vec!["13", "2", "1"])
+        );
+        assert_eq!(
+            scan(
+                "Operation: new = old {} {}",
+                "  Operation: new = old * old\r"
+            ),
+            Ok(vec!["*", "old"])
+        );
+        assert_eq!(scan("Monkey {}:", "Monkey 0:"), Ok(vec!["0"]));
+        assert!(scan("move {} from {} to {}", "move 13 from 2").is_err());
+        assert!(scan("move {} from {} to {}", "move  from 2 to 1").is_err());
+        assert!(scan("Monkey {}:", "Monkey 0: extra").is_err());
+        assert!(scan("{} -> {}", "a => b").is_err());
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(blocks("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+        assert_eq!(blocks("a\r\nb\r\n\r\nc\r\n"), vec!["a\r\nb", "c"]);
+        assert_eq!(blocks("\n\na\n  \nc\n\n\n"), vec!["a", "c"]);
+        assert_eq!(blocks("a"), vec!["a"]);
+        assert!(blocks("").is_empty());
+    }
+
+    #[test]
+    fn test_error_message() {
+        let error = scan("Monkey {}:", "Monkey").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "\"Monkey\" doesn't match pattern \"Monkey {}:\""
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/* Progress reporting and cancellation for the solvers that take a while (day 11 part 2, day 12 part 2,
+ * day 15 part 2). Those report their steps through a `Progress`, which stops them with `Cancelled`
+ * once its token is cancelled, i.e. by the `--timeout` of the tools */
+
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cancelled {
+    pub done: u64,
+    pub total: u64,
+    // What the solver found so far
+    pub diagnostics: String,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Cancelled after {} of {} steps: {}",
+            self.done, self.total, self.diagnostics
+        )
+    }
+}
+
+pub struct Progress<'a> {
+    report: Option<Box<dyn FnMut(u64, u64) + 'a>>,
+    token: CancelToken,
+}
+
+impl<'a> Progress<'a> {
+    /* For solving without reporting, that is never cancelled */
+    pub fn none() -> Progress<'a> {
+        Progress {
+            report: None,
+            token: CancelToken::new(),
+        }
+    }
+
+    pub fn new<F: FnMut(u64, u64) + 'a>(report: F, token: CancelToken) -> Progress<'a> {
+        Progress {
+            report: Some(Box::new(report)),
+            token,
+        }
+    }
+
+    /* Reports that `done` of `total` steps are done. The diagnostics are only built when cancelled */
+    pub fn step<D: FnOnce() -> String>(
+        &mut self,
+        done: u64,
+        total: u64,
+        diagnostics: D,
+    ) -> Result<(), Cancelled> {
+        if let Some(report) = self.report.as_mut() {
+            report(done, total);
+        }
+        if self.token.is_cancelled() {
+            return Err(Cancelled {
+                done,
+                total,
+                diagnostics: diagnostics(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let mut reported = Vec::new();
+        let token = CancelToken::new();
+        let mut progress = Progress::new(|done, total| reported.push((done, total)), token.clone());
+        assert_eq!(progress.step(1, 3, || unreachable!()), Ok(()));
+        token.cancel();
+        assert_eq!(
+            progress.step(2, 3, || "almost".to_string()),
+            Err(Cancelled {
+                done: 2,
+                total: 3,
+                diagnostics: "almost".to_string()
+            })
+        );
+        drop(progress);
+        assert_eq!(reported, vec![(1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn test_none() {
+        assert_eq!(Progress::none().step(1, 1, String::new), Ok(()));
+    }
+}
@@ -0,0 +1,122 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/* Snapshot tests for intermediate states of the simulations, stored in snapshots/<name>.snap.
+ * A changed state fails the test with a diff, review it and accept with `AOC_UPDATE_SNAPSHOTS=1 cargo test` */
+
+const CONTEXT: usize = 2;
+
+fn path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/* Line diff based on the longest common subsequence, so that an inserted state doesn't show up as
+ * every following line having changed. Unchanged lines are prefixed with "  ", others with "- " and "+ " */
+fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/* Only the changed lines with some context around them */
+fn changes(diff: &[String]) -> String {
+    let changed: Vec<usize> = (0..diff.len())
+        .filter(|&i| !diff[i].starts_with("  "))
+        .collect();
+    let mut shown = String::new();
+    let mut last_shown = None;
+    for (i, line) in diff.iter().enumerate() {
+        let near_change = changed
+            .iter()
+            .any(|&c| i + CONTEXT >= c && i <= c + CONTEXT);
+        if !near_change {
+            continue;
+        }
+        if last_shown.map_or(i > 0, |last: usize| last + 1 < i) {
+            shown.push_str("  ...\n");
+        }
+        shown.push_str(line);
+        shown.push('\n');
+        last_shown = Some(i);
+    }
+    shown
+}
+
+pub(crate) fn assert_snapshot(name: &str, actual: &str) {
+    let path = path(name);
+    if env::var("AOC_UPDATE_SNAPSHOTS").is_ok() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = match fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(_) => panic!(
+            "Snapshot {} is missing, create it with `AOC_UPDATE_SNAPSHOTS=1 cargo test`",
+            path.display()
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "Snapshot {} changed (- expected, + actual):\n{}\nIf this is intended, update it with `AOC_UPDATE_SNAPSHOTS=1 cargo test`",
+            path.display(),
+            changes(&diff(&expected, actual))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nb\nc\n"),
+            vec!["  a", "+ x", "  b", "  c"]
+        );
+        assert_eq!(diff("a\nb\n", "a\nc\n"), vec!["  a", "- b", "+ c"]);
+    }
+
+    #[test]
+    fn test_changes() {
+        let expected = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let actual = "1\n2\n3\n4\n5\n6\n7\nx\n";
+        assert_eq!(
+            changes(&diff(expected, actual)),
+            "  ...\n  6\n  7\n- 8\n+ x\n"
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::*;
+
+use crate::progress::{Cancelled, Progress};
+use std::io::BufRead;
+
+/* Plain lookup table of all solutions, for tooling that can't use the main generated by aoc-runner.
+ * Inputs are normalized (see `utils::normalize`) before they are passed to the solutions */
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    // Name of an alternative implementation, like `#[aoc(dayN, partM, Name)]`. None for the default one
+    pub name: Option<&'static str>,
+    pub solve: fn(&str) -> String,
+}
+
+impl Solution {
+    pub fn name(&self) -> &'static str {
+        self.name.unwrap_or("default")
+    }
+}
+
+macro_rules! solution {
+    ($day:expr, $part:expr, $solver:path) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: None,
+            solve: |input| $solver(&utils::normalize(input)).to_string(),
+        }
+    };
+    ($day:expr, $part:expr, $name:literal, $solver:path) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: Some($name),
+            solve: |input| $solver(&utils::normalize(input)).to_string(),
+        }
+    };
+}
+
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, day01::part1),
+    solution!(1, 2, day01::part2),
+    solution!(2, 1, day02::part1),
+    solution!(2, 2, day02::part2),
+    solution!(3, 1, day03::part1),
+    solution!(3, 2, day03::part2),
+    solution!(4, 1, day04::part1),
+    solution!(4, 2, day04::part2),
+    solution!(5, 1, day05::part1),
+    solution!(5, 2, day05::part2),
+    solution!(6, 1, day06::part1),
+    solution!(6, 1, "Bitmask", day06::part1_bitmask),
+    solution!(6, 2, day06::part2),
+    solution!(6, 2, "Bitmask", day06::part2_bitmask),
+    solution!(7, 1, day07::part1),
+    solution!(7, 2, day07::part2),
+    solution!(8, 1, day08::part1),
+    solution!(8, 2, day08::part2),
+    solution!(9, 1, day09::part1),
+    solution!(9, 2, day09::part2),
+    solution!(10, 1, day10::part1),
+    solution!(10, 2, day10::part2),
+    solution!(11, 1, day11::part1),
+    solution!(11, 2, day11::part2),
+    solution!(12, 1, day12::part1),
+    solution!(12, 2, day12::part2),
+    solution!(13, 1, day13::part1),
+    solution!(13, 2, day13::part2),
+    solution!(14, 1, day14::part1),
+    solution!(14, 2, day14::part2),
+    solution!(14, 2, "Grid", day14::part2_grid),
+    solution!(15, 1, day15::part1),
+    solution!(15, 2, day15::part2),
+    solution!(16, 1, day16::part1),
+    solution!(16, 2, day16::part2),
+    solution!(17, 1, day17::part1),
+    solution!(17, 2, day17::part2),
+    solution!(18, 1, day18::part1),
+    solution!(18, 2, day18::part2),
+    solution!(19, 1, day19::part1),
+    solution!(19, 2, day19::part2),
+    solution!(20, 1, day20::part1),
+    solution!(20, 1, "Naive", day20::part1_naive),
+    solution!(20, 2, day20::part2),
+    solution!(20, 2, "Naive", day20::part2_naive),
+    solution!(21, 1, day21::part1),
+    solution!(21, 2, day21::part2),
+    solution!(22, 1, day22::part1),
+    solution!(22, 2, day22::part2),
+    solution!(23, 1, day23::part1),
+    solution!(23, 2, day23::part2),
+    solution!(24, 1, day24::part1),
+    solution!(24, 2, day24::part2),
+    solution!(25, 1, day25::part1),
+];
+
+/* Solutions reading their input incrementally, for inputs that don't fit in memory */
+pub struct StreamingSolution {
+    pub day: u32,
+    pub part: u32,
+    pub solve: fn(&mut dyn BufRead) -> String,
+}
+
+macro_rules! streaming_solution {
+    ($day:expr, $part:expr, $solver:path) => {
+        StreamingSolution {
+            day: $day,
+            part: $part,
+            solve: |reader| $solver(reader).to_string(),
+        }
+    };
+}
+
+pub const STREAMING_SOLUTIONS: &[StreamingSolution] = &[
+    streaming_solution!(1, 1, day01::part1_reader),
+    streaming_solution!(1, 2, day01::part2_reader),
+    streaming_solution!(2, 1, day02::part1_reader),
+    streaming_solution!(2, 2, day02::part2_reader),
+    streaming_solution!(3, 1, day03::part1_reader),
+    streaming_solution!(3, 2, day03::part2_reader),
+    streaming_solution!(4, 1, day04::part1_reader),
+    streaming_solution!(4, 2, day04::part2_reader),
+    streaming_solution!(6, 1, day06::part1_reader),
+    streaming_solution!(6, 2, day06::part2_reader),
+    streaming_solution!(9, 1, day09::part1_reader),
+    streaming_solution!(9, 2, day09::part2_reader),
+    streaming_solution!(10, 1, day10::part1_reader),
+    streaming_solution!(10, 2, day10::part2_reader),
+];
+
+pub fn find_streaming(day: u32, part: u32) -> Option<&'static StreamingSolution> {
+    STREAMING_SOLUTIONS
+        .iter()
+        .find(|solution| solution.day == day && solution.part == part)
+}
+
+/* Solutions reporting their progress, so that they can show a progress bar and be cancelled */
+pub struct ProgressSolution {
+    pub day: u32,
+    pub part: u32,
+    pub solve: fn(&str, &mut Progress) -> Result<String, Cancelled>,
+}
+
+macro_rules! progress_solution {
+    ($day:expr, $part:expr, $solver:path) => {
+        ProgressSolution {
+            day: $day,
+            part: $part,
+            solve: |input, progress| {
+                $solver(&utils::normalize(input), progress).map(|answer| answer.to_string())
+            },
+        }
+    };
+}
+
+pub const PROGRESS_SOLUTIONS: &[ProgressSolution] = &[
+    progress_solution!(11, 2, day11::part2_with_progress),
+    progress_solution!(12, 2, day12::part2_with_progress),
+    progress_solution!(15, 2, day15::part2_with_progress),
+];
+
+pub fn find_with_progress(day: u32, part: u32) -> Option<&'static ProgressSolution> {
+    PROGRESS_SOLUTIONS
+        .iter()
+        .find(|solution| solution.day == day && solution.part == part)
+}
+
+/* The default solutions of a day */
+pub fn for_day(day: u32) -> impl Iterator<Item = &'static Solution> {
+    SOLUTIONS
+        .iter()
+        .filter(move |solution| solution.day == day && solution.name.is_none())
+}
+
+pub fn find(day: u32, part: u32) -> Option<&'static Solution> {
+    for_day(day).find(|solution| solution.part == part)
+}
+
+/* All implementations of a part, the default one first */
+pub fn variants(day: u32, part: u32) -> impl Iterator<Item = &'static Solution> {
+    SOLUTIONS
+        .iter()
+        .filter(move |solution| solution.day == day && solution.part == part)
+}
+
+/* Runs all implementations of a part, returning (name, answer) for the ones disagreeing with the default */
+pub fn cross_check(day: u32, part: u32, input: &str) -> Vec<(&'static str, String)> {
+    let mut answers =
+        variants(day, part).map(|solution| (solution.name(), (solution.solve)(input)));
+    let expected = match answers.next() {
+        Some((_, answer)) => answer,
+        None => return vec![],
+    };
+    answers.filter(|(_, answer)| *answer != expected).collect()
+}
+
+/* Checks that the sample gives the same answers however it was saved, for all ways of solving a day */
+#[cfg(test)]
+pub(crate) fn assert_input_variants(day: u32, input: &str) {
+    for (variant, content) in utils::input_variants(input) {
+        for solution in SOLUTIONS.iter().filter(|solution| solution.day == day) {
+            assert_eq!(
+                (solution.solve)(&content),
+                (solution.solve)(input),
+                "Day {} - Part {} ({}) with {}",
+                day,
+                solution.part,
+                solution.name(),
+                variant
+            );
+        }
+        for solution in STREAMING_SOLUTIONS.iter().filter(|s| s.day == day) {
+            assert_eq!(
+                (solution.solve)(&mut content.as_bytes()),
+                (solution.solve)(&mut input.as_bytes()),
+                "Day {} - Part {} (streaming) with {}",
+                day,
+                solution.part,
+                variant
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::generators::{self, Rng};
+    use crate::progress::CancelToken;
+
+    #[test]
+    fn test_find() {
+        let solution = find(1, 2).unwrap();
+        assert_eq!((solution.day, solution.part), (1, 2));
+        assert_eq!((solution.solve)("1\n2\n\n3\n\n4\n"), "10");
+        assert!(find(1, 3).is_none());
+    }
+
+    #[test]
+    fn test_for_day() {
+        let parts: Vec<u32> = for_day(6).map(|solution| solution.part).collect();
+        assert_eq!(parts, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_variants() {
+        let names: Vec<&str> = variants(14, 2).map(|solution| solution.name()).collect();
+        assert_eq!(names, vec!["default", "Grid"]);
+    }
+
+    #[test]
+    fn test_streaming_agrees() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+        for part in 1..=2 {
+            let streaming = find_streaming(4, part).unwrap();
+            assert_eq!(
+                (streaming.solve)(&mut input.as_bytes()),
+                (find(4, part).unwrap().solve)(input)
+            );
+        }
+        assert!(find_streaming(5, 1).is_none());
+    }
+
+    #[test]
+    fn test_progress_agrees() {
+        let input = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+        let mut steps = 0;
+        let mut progress = Progress::new(|_, _| steps += 1, CancelToken::new());
+        assert_eq!(
+            (find_with_progress(12, 2).unwrap().solve)(input, &mut progress),
+            Ok((find(12, 2).unwrap().solve)(input))
+        );
+        drop(progress);
+        assert_eq!(steps, 5);
+        assert!(find_with_progress(12, 1).is_none());
+    }
+
+    #[test]
+    fn test_variants_agree_on_generated_inputs() {
+        let mut rng = Rng::new(2022);
+        for (day, generate) in generators::GENERATORS {
+            for _ in 0..5 {
+                let input = generate(&mut rng);
+                for part in 1..=2 {
+                    assert_eq!(cross_check(*day, part, &input), vec![], "{}", input);
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,26 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::io::BufRead;
+use std::iter;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs::File, io, path::Path};
+
+/* Buffered reader for the solutions taking a BufRead, so that inputs don't have to fit in memory.
+ * The solvers themselves never touch the filesystem, these aren't there in the wasm build */
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open<P>(filename: P) -> io::Result<io::BufReader<File>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(filename)?;
+    Ok(io::BufReader::new(file))
+}
 
 /* https://doc.rust-lang.org/stable/rust-by-example/std_misc/file/read_lines.html */
+#[cfg(not(target_arch = "wasm32"))]
 pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(open(filename)?.lines())
 }
 
 pub fn get_path(day: &'static str, test: bool) -> String {
@@ -18,3 +30,86 @@ pub fn get_path(day: &'static str, test: bool) -> String {
         return format!("./inputs/{}.sample.txt", day);
     }
 }
+
+/* Lines of a reader, normalized like `normalize` does. Read errors are treated like malformed input */
+pub fn lines<R: BufRead>(reader: R) -> impl Iterator<Item = String> {
+    let mut lines = reader
+        .lines()
+        .map(|line| line.unwrap().trim_end().to_string());
+    // Blank lines are held back until a non-blank line follows, so that trailing ones are dropped
+    let mut blank_lines = 0;
+    let mut next_line = None;
+    iter::from_fn(move || {
+        if blank_lines == 0 {
+            if let Some(line) = next_line.take() {
+                return Some(line);
+            }
+            loop {
+                let line = lines.next()?;
+                if line.is_empty() {
+                    blank_lines += 1;
+                } else if blank_lines == 0 {
+                    return Some(line);
+                } else {
+                    next_line = Some(line);
+                    break;
+                }
+            }
+        }
+        blank_lines -= 1;
+        Some(String::new())
+    })
+}
+
+/* Puzzle input as the solutions expect it: LF line endings, no trailing whitespace on lines,
+ * no trailing blank lines and a final newline. Inputs saved on Windows or copied from the
+ * browser tend to differ in exactly those */
+pub fn normalize(content: &str) -> String {
+    let mut normalized = String::with_capacity(content.len() + 1);
+    for line in content.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    let end = normalized.trim_end_matches('\n').len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/* The sample in the ways it could have been saved, for checking that normalizing takes care of them */
+#[cfg(test)]
+pub(crate) fn input_variants(input: &str) -> Vec<(&'static str, String)> {
+    let trimmed = input.trim_end_matches('\n');
+    vec![
+        ("CRLF line endings", input.replace('\n', "\r\n")),
+        ("no final newline", trimmed.to_string()),
+        ("trailing blank lines", format!("{}\n\n\n", trimmed)),
+        (
+            "trailing whitespace",
+            trimmed.replace('\n', "  \n") + " \t\n",
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a \r\n\r\nb\r\n\r\n\r\n"), "a\n\nb\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("  a\t\n"), "  a\n");
+        assert_eq!(normalize("\n\n"), "");
+    }
+
+    #[test]
+    fn test_lines() {
+        let read = |content: &str| lines(content.as_bytes()).collect::<Vec<String>>();
+        assert_eq!(read("a \r\n\r\nb\r\n\r\n\r\n"), vec!["a", "", "b"]);
+        assert_eq!(read("\n\na\n\n\nb"), vec!["", "", "a", "", "", "b"]);
+    }
+}
@@ -0,0 +1,24 @@
+use std::alloc::{self, Layout};
+
+/* Memory management for the JS wrapper in wasm/aoc.mjs, which passes strings to `aoc_solve` through
+ * buffers in the module's memory. Only built for wasm32, see README */
+
+// Enough for the usize `answer_len` of `aoc_solve`
+const ALIGN: usize = 8;
+
+fn layout(len: usize) -> Layout {
+    Layout::from_size_align(len.max(1), ALIGN).unwrap()
+}
+
+#[no_mangle]
+pub extern "C" fn aoc_alloc(len: usize) -> *mut u8 {
+    unsafe { alloc::alloc(layout(len)) }
+}
+
+/// # Safety
+///
+/// `ptr` must come from `aoc_alloc(len)` and not have been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free(ptr: *mut u8, len: usize) {
+    alloc::dealloc(ptr, layout(len))
+}
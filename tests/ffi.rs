@@ -0,0 +1,52 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/* Builds tests/ffi/test_aoc.c against the cdylib and runs it, skipped when there is no C compiler */
+
+fn build_cdylib(manifest_dir: &Path) -> PathBuf {
+    // cargo doesn't build the cdylib for tests. Use a separate target directory, the one of
+    // the running `cargo test` is locked
+    let target_dir = manifest_dir.join("target/ffi-test");
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["build", "--quiet", "--lib", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(manifest_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the cdylib failed");
+    target_dir.join("debug")
+}
+
+#[test]
+fn test_c_program() {
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    if Command::new(&compiler).arg("--version").output().is_err() {
+        eprintln!("No C compiler found, skipping");
+        return;
+    }
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = build_cdylib(manifest_dir);
+    let program = lib_dir.join("test_aoc");
+    let status = Command::new(&compiler)
+        .arg(manifest_dir.join("tests/ffi/test_aoc.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-ladvent_of_code_2022")
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling test_aoc.c failed");
+
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .env("DYLD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "test_aoc failed:\n{}", stdout);
+}
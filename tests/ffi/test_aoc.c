@@ -0,0 +1,57 @@
+/* Exercises the C ABI on the sample inputs, built and run by tests/ffi.rs */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void check(uint32_t day, uint32_t part, const char *input, int32_t expected_code, const char *expected)
+{
+    char answer[64] = {0};
+    size_t answer_len = 0;
+    int32_t code = aoc_solve(2022, day, part, (const uint8_t *)input, strlen(input),
+                             answer, sizeof(answer), &answer_len);
+    if (code != expected_code || (expected != NULL && strcmp(answer, expected) != 0)) {
+        printf("FAILED day %u part %u: got code %d answer '%s', expected code %d answer '%s'\n",
+               day, part, code, answer, expected_code, expected ? expected : "");
+        failures++;
+    } else {
+        printf("ok day %u part %u: code %d answer '%s'\n", day, part, code, answer);
+    }
+}
+
+int main(void)
+{
+    const char *day1 = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    const char *day2 = "A Y\nB X\nC Z\n";
+    const char *day4 = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+    const char *day6 = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n";
+
+    check(1, 1, day1, AOC_OK, "24000");
+    check(1, 2, day1, AOC_OK, "45000");
+    check(2, 1, day2, AOC_OK, "15");
+    check(2, 2, day2, AOC_OK, "12");
+    check(4, 1, day4, AOC_OK, "2");
+    check(4, 2, day4, AOC_OK, "4");
+    check(6, 1, day6, AOC_OK, "11");
+    check(6, 2, day6, AOC_OK, "26");
+
+    /* Errors are reported as codes, panics don't cross the boundary */
+    check(1, 3, day1, AOC_ERR_UNKNOWN_SOLUTION, NULL);
+    check(1, 1, "not a number\n", AOC_ERR_PANIC, NULL);
+
+    char small[2];
+    size_t answer_len = 0;
+    int32_t code = aoc_solve(2022, 1, 1, (const uint8_t *)day1, strlen(day1), small, sizeof(small), &answer_len);
+    if (code != AOC_ERR_BUFFER_TOO_SMALL || answer_len != 5) {
+        printf("FAILED small buffer: got code %d length %zu\n", code, answer_len);
+        failures++;
+    }
+    if (aoc_solve(2021, 1, 1, (const uint8_t *)day1, strlen(day1), small, sizeof(small), NULL) != AOC_ERR_UNKNOWN_YEAR) {
+        printf("FAILED unknown year\n");
+        failures++;
+    }
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,64 @@
+use advent_of_code_2022::solutions::{self, SOLUTIONS};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/* Runs the solutions on the samples written by `tools extract`, if there are any */
+
+fn input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input/2022")
+}
+
+fn parse_expected(content: &str) -> Vec<(u32, String)> {
+    // Lines like `part1: 24000`
+    content
+        .lines()
+        .filter_map(|line| line.strip_prefix("part"))
+        .filter_map(|line| line.split_once(": "))
+        .filter_map(|(part, answer)| Some((part.parse::<u32>().ok()?, answer.to_string())))
+        .collect()
+}
+
+fn read_sample(day: u32, part: u32) -> Option<String> {
+    let part_sample = input_dir().join(format!("day{}.sample.part{}.txt", day, part));
+    let sample = input_dir().join(format!("day{}.sample.txt", day));
+    fs::read_to_string(part_sample)
+        .or_else(|_| fs::read_to_string(sample))
+        .ok()
+}
+
+#[test]
+fn test_extracted_samples() {
+    let mut days: Vec<u32> = SOLUTIONS.iter().map(|solution| solution.day).collect();
+    days.dedup();
+
+    let mut failures = Vec::new();
+    for day in days {
+        let expected_path = input_dir().join(format!("day{}.sample.expected.txt", day));
+        let expected = match fs::read_to_string(expected_path) {
+            Ok(content) => parse_expected(&content),
+            Err(_) => continue,
+        };
+        for (part, answer) in expected {
+            let (solution, sample) = match (solutions::find(day, part), read_sample(day, part)) {
+                (Some(solution), Some(sample)) => (solution, sample),
+                _ => continue,
+            };
+            let result = (solution.solve)(&sample);
+            if result != answer {
+                failures.push(format!(
+                    "Day {} - Part {}: expected {}, got {}",
+                    day, part, answer, result
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_parse_expected() {
+    assert_eq!(
+        parse_expected("part1: 24000\npart2: 45000\n"),
+        vec![(1, "24000".to_string()), (2, "45000".to_string())]
+    );
+}
@@ -0,0 +1,65 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/* Builds the library for wasm32-unknown-unknown and runs tests/wasm/test_aoc.mjs on it with node,
+ * skipped when node or the wasm32 target isn't installed */
+
+const TARGET: &str = "wasm32-unknown-unknown";
+
+fn target_installed() -> bool {
+    // rustc prints the directory even when the target isn't installed, check that it exists
+    let output = Command::new("rustc")
+        .args(["--print", "target-libdir", "--target", TARGET])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            Path::new(String::from_utf8_lossy(&output.stdout).trim()).exists()
+        }
+        _ => false,
+    }
+}
+
+fn build_wasm(manifest_dir: &Path) -> PathBuf {
+    // Separate target directory, the one of the running `cargo test` is locked
+    let target_dir = manifest_dir.join("target/wasm-test");
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["build", "--quiet", "--lib", "--release", "--target", TARGET])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .current_dir(manifest_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the wasm module failed");
+    target_dir
+        .join(TARGET)
+        .join("release/advent_of_code_2022.wasm")
+}
+
+#[test]
+fn test_wasm_module() {
+    let node = env::var("NODE").unwrap_or_else(|_| "node".to_string());
+    if Command::new(&node).arg("--version").output().is_err() {
+        eprintln!("No node found, skipping");
+        return;
+    }
+    if !target_installed() {
+        eprintln!("No {} target installed, skipping", TARGET);
+        return;
+    }
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let module = build_wasm(manifest_dir);
+    let output = Command::new(&node)
+        .arg(manifest_dir.join("tests/wasm/test_aoc.mjs"))
+        .arg(&module)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "test_aoc.mjs failed:\n{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
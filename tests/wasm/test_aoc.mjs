@@ -0,0 +1,64 @@
+/* Exercises the wasm module through wasm/aoc.mjs on the sample inputs, run by tests/wasm.rs as
+ * `node tests/wasm/test_aoc.mjs <module.wasm>` */
+import { readFileSync } from "node:fs";
+import { AocError, load } from "../../wasm/aoc.mjs";
+
+const samples = {
+  1: "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+  2: "A Y\nB X\nC Z\n",
+  4: "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n",
+  5: "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n",
+  6: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n",
+  14: "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n",
+};
+
+const expected = [
+  [1, 1, "24000"],
+  [1, 2, "45000"],
+  [2, 1, "15"],
+  [2, 2, "12"],
+  [4, 1, "2"],
+  [4, 2, "4"],
+  [5, 1, "CMZ"],
+  [5, 2, "MCD"],
+  [6, 1, "11"],
+  [6, 2, "26"],
+  [14, 1, "24"],
+  [14, 2, "93"],
+];
+
+let failures = 0;
+
+function check(description, actual, wanted) {
+  if (actual === wanted) {
+    console.log(`ok ${description}: ${JSON.stringify(actual)}`);
+  } else {
+    console.log(`FAILED ${description}: got ${JSON.stringify(actual)}, expected ${JSON.stringify(wanted)}`);
+    failures++;
+  }
+}
+
+function errorCode(f) {
+  try {
+    f();
+    return null;
+  } catch (e) {
+    return e instanceof AocError ? e.code : e;
+  }
+}
+
+const aoc = await load(readFileSync(process.argv[2]));
+
+for (const [day, part, answer] of expected) {
+  check(`day ${day} part ${part}`, aoc.solve(day, part, samples[day]), answer);
+}
+
+// Answers longer than the first output buffer are retried with a larger one
+const screen = Array(6).fill(`###${".".repeat(37)}`).join("\n");
+check("long answer", aoc.solve(10, 2, "noop\n".repeat(240)), screen);
+
+check("unknown solution", errorCode(() => aoc.solve(6, 3, samples[6])), 3);
+// Last, the instance shouldn't be used anymore after a panic
+check("panic", errorCode(() => aoc.solve(1, 1, "not a number")), 6);
+
+process.exit(failures === 0 ? 0 : 1);
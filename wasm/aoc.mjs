@@ -0,0 +1,91 @@
+/*
+ * JS API for the solutions compiled to wasm32-unknown-unknown, see the README.
+ *
+ *   import { load } from "./aoc.mjs";
+ *   const aoc = await load(await (await fetch("advent_of_code_2022.wasm")).arrayBuffer());
+ *   aoc.solve(1, 1, input); // "24000"
+ *
+ * Errors of aoc_solve (see include/aoc.h) are thrown as AocError with their code.
+ */
+
+const YEAR = 2022;
+
+const AOC_OK = 0;
+const AOC_ERR_BUFFER_TOO_SMALL = 5;
+
+const ERRORS = {
+  1: "NULL pointer",
+  2: "No solutions for this year",
+  3: "No solution for this day and part",
+  4: "The input is not valid UTF-8",
+  5: "Buffer too small for the answer",
+  6: "The solution panicked, most likely on malformed input",
+};
+
+export class AocError extends Error {
+  constructor(code, message) {
+    super(message ?? ERRORS[code] ?? `Unknown error ${code}`);
+    this.name = "AocError";
+    this.code = code;
+  }
+}
+
+class Solver {
+  constructor(exports) {
+    this.exports = exports;
+  }
+
+  /* Copies bytes into a fresh buffer in the module's memory */
+  withBuffer(len, use) {
+    const ptr = this.exports.aoc_alloc(len);
+    try {
+      return use(ptr);
+    } finally {
+      this.exports.aoc_free(ptr, len);
+    }
+  }
+
+  solve(day, part, input) {
+    const bytes = new TextEncoder().encode(input);
+    return this.withBuffer(bytes.length, (inputPtr) => {
+      new Uint8Array(this.exports.memory.buffer, inputPtr, bytes.length).set(bytes);
+      // Most answers are short, retry with the exact size otherwise (i.e. the letters of day 10)
+      let outLen = 64;
+      for (;;) {
+        const result = this.withBuffer(outLen, (outPtr) =>
+          this.withBuffer(8, (answerLenPtr) => {
+            let code;
+            try {
+              code = this.exports.aoc_solve(
+                YEAR, day, part, inputPtr, bytes.length, outPtr, outLen, answerLenPtr
+              );
+            } catch (e) {
+              // Panics abort in wasm32-unknown-unknown, they end up here as a trap
+              throw new AocError(6, `${ERRORS[6]} (${e.message})`);
+            }
+            // Re-read memory.buffer, it is replaced when the memory grows
+            const memory = this.exports.memory.buffer;
+            const answerLen = new DataView(memory).getUint32(answerLenPtr, true);
+            if (code === AOC_OK) {
+              return new TextDecoder().decode(new Uint8Array(memory, outPtr, answerLen));
+            }
+            if (code === AOC_ERR_BUFFER_TOO_SMALL) {
+              return { retryWith: answerLen + 1 };
+            }
+            throw new AocError(code);
+          })
+        );
+        if (typeof result === "string") {
+          return result;
+        }
+        outLen = result.retryWith;
+      }
+    });
+  }
+}
+
+/* Instantiates the module from its bytes (an ArrayBuffer or typed array) */
+export async function load(bytes) {
+  const { instance } = await WebAssembly.instantiate(bytes, {});
+  return new Solver(instance.exports);
+}